@@ -0,0 +1,351 @@
+use crate::fixture::is_fixture_attribute;
+use crate::utils::fmt_syn;
+use crate::TokenStream2;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Error, Expr, FnArg, Ident, ItemFn, Pat, PatType, Token};
+
+/// Argument given by parameter name, e.g. `height = 2`.
+#[derive(Clone, Debug)]
+pub struct KeywordArg {
+    pub name: Ident,
+    pub value: Expr,
+}
+
+/// Values of parameters used by test cases which do not give them, declared with function level
+/// `#[test_case_defaults(height = 1)]`.
+#[derive(Clone, Debug, Default)]
+pub struct TestCaseDefaults(Vec<KeywordArg>);
+
+impl Parse for KeywordArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let value = input.parse()?;
+
+        Ok(Self { name, value })
+    }
+}
+
+impl Parse for TestCaseDefaults {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let defaults = Punctuated::<KeywordArg, Token![,]>::parse_terminated(input)?;
+
+        Ok(Self(defaults.into_iter().collect()))
+    }
+}
+
+impl KeywordArg {
+    /// `width = 10` written among test case arguments is parsed as assignment expression.
+    fn from_expr(expr: &Expr) -> Option<Self> {
+        let assign = match expr {
+            Expr::Assign(assign) => assign,
+            _ => return None,
+        };
+        let path = match assign.left.as_ref() {
+            Expr::Path(path) => path,
+            _ => return None,
+        };
+        if path.qself.is_some() || !assign.attrs.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name: path.path.get_ident()?.clone(),
+            value: assign.right.as_ref().clone(),
+        })
+    }
+}
+
+/// Attribute the arguments come from, used to explain argument count mismatch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArgumentsSource {
+    #[default]
+    TestCase,
+    TestMatrix,
+}
+
+fn param_name(param: &PatType) -> Option<&Ident> {
+    match param.pat.as_ref() {
+        Pat::Ident(pat) => Some(&pat.ident),
+        _ => None,
+    }
+}
+
+/// Arguments of the call to test function. Parameters receive, in order of precedence:
+///
+/// * positional `args` in order, skipping parameters marked with `#[fixture]`,
+/// * keyword `args` (`height = 2`) following positional ones, matched by parameter name,
+/// * `defaults` declared for the function,
+/// * for `#[fixture]` parameters, value constructed by calling function of the same name.
+///
+/// Method receiver is not a part of the call arguments. `skip_last` leaves out the last parameter,
+/// which receives value of `test_case_setup`.
+///
+/// Mismatch between number of positional arguments and parameters is reported at `span` of the
+/// attribute.
+pub(crate) fn call_arguments(
+    item: &ItemFn,
+    args: &Punctuated<Expr, Token![,]>,
+    defaults: &TestCaseDefaults,
+    skip_last: bool,
+    source: ArgumentsSource,
+    span: Span,
+) -> Result<Vec<TokenStream2>, Error> {
+    let params: Vec<&PatType> = item
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(param) => Some(param),
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let params = &params[..params.len().saturating_sub(usize::from(skip_last))];
+
+    let mut positional = vec![];
+    let mut keyword = vec![];
+    for arg in args {
+        match KeywordArg::from_expr(arg) {
+            Some(arg) => keyword.push(arg),
+            None if !keyword.is_empty() => {
+                return Err(Error::new_spanned(
+                    arg,
+                    "positional arguments have to precede keyword arguments",
+                ))
+            }
+            None => positional.push(arg),
+        }
+    }
+    for (idx, arg) in keyword.iter().enumerate() {
+        if keyword[..idx].iter().any(|other| other.name == arg.name) {
+            return Err(Error::new_spanned(
+                &arg.name,
+                format!("parameter `{}` is given more than once", arg.name),
+            ));
+        }
+    }
+    for arg in keyword.iter().chain(&defaults.0) {
+        if !params
+            .iter()
+            .any(|param| param_name(param) == Some(&arg.name))
+        {
+            return Err(Error::new_spanned(
+                &arg.name,
+                format!("test function has no parameter `{}`", arg.name),
+            ));
+        }
+    }
+
+    let by_name = !keyword.is_empty() || !defaults.0.is_empty();
+    let given = positional.len();
+    let mut positional = positional.into_iter();
+    let mut values = vec![];
+    let mut missing = vec![];
+
+    for param in params {
+        let name = param_name(param);
+        let keyword = keyword.iter().find(|arg| Some(&arg.name) == name);
+        let is_fixture = param.attrs.iter().any(is_fixture_attribute);
+
+        if let Some(arg) = (!is_fixture).then(|| positional.next()).flatten() {
+            if let Some(keyword) = keyword {
+                return Err(Error::new_spanned(
+                    &keyword.name,
+                    format!("parameter `{}` is already given positionally", keyword.name),
+                ));
+            }
+            values.push(quote! { #arg });
+        } else if let Some(keyword) = keyword {
+            let value = &keyword.value;
+            values.push(quote! { #value });
+        } else if let Some(default) = defaults.0.iter().find(|arg| Some(&arg.name) == name) {
+            let value = &default.value;
+            values.push(quote! { #value });
+        } else if is_fixture {
+            let fixture = match name {
+                Some(fixture) => fixture,
+                None => return Err(Error::new_spanned(
+                    &param.pat,
+                    "fixture parameter has to be a plain identifier naming function which creates it",
+                )),
+            };
+            values.push(quote! { #fixture() });
+        } else if by_name {
+            let msg = match name {
+                Some(name) => format!("missing value of parameter `{name}`, give it as `{name} = ...` or declare it in `#[test_case_defaults(...)]`"),
+                None => "missing value of parameter, give it positionally".to_string(),
+            };
+            return Err(Error::new_spanned(args, msg));
+        } else {
+            missing.push(match name {
+                Some(name) => format!("`{name}`"),
+                None => format!("`{}`", fmt_syn(&param.pat)),
+            });
+        }
+    }
+    let extra: Vec<&Expr> = positional.collect();
+
+    if !missing.is_empty() || !extra.is_empty() {
+        let expected = given + missing.len() - extra.len();
+        let (attr, unit, units) = match source {
+            ArgumentsSource::TestCase => ("test case", "argument", "arguments"),
+            ArgumentsSource::TestMatrix => ("test matrix", "axis", "axes"),
+        };
+        let details = if !missing.is_empty() {
+            let (values, parameters) = match missing.len() {
+                1 => ("value", "parameter"),
+                _ => ("values", "parameters"),
+            };
+            format!("missing {values} of {parameters} {}", missing.join(", "))
+        } else {
+            let extra = match source {
+                ArgumentsSource::TestCase => extra
+                    .iter()
+                    .map(|arg| format!("`{}`", fmt_syn(*arg)))
+                    .collect::<Vec<_>>(),
+                ArgumentsSource::TestMatrix => (expected + 1..=given)
+                    .map(|axis| format!("{axis}"))
+                    .collect(),
+            };
+            let has = if extra.len() == 1 { "has" } else { "have" };
+            let unit = if extra.len() == 1 { unit } else { units };
+            format!("{unit} {} {has} no parameter", extra.join(", "))
+        };
+
+        return Err(Error::new(
+            span,
+            format!(
+                "{attr} has {given} {}, but test function expects {expected}: {details}",
+                if given == 1 { unit } else { units },
+            ),
+        ));
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arguments::{call_arguments, ArgumentsSource, TestCaseDefaults};
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::punctuated::Punctuated;
+    use syn::{parse_quote, Expr, ItemFn, Token};
+
+    fn render(
+        item: ItemFn,
+        args: Punctuated<Expr, Token![,]>,
+        defaults: TestCaseDefaults,
+    ) -> String {
+        let source = ArgumentsSource::TestCase;
+        match call_arguments(&item, &args, &defaults, false, source, Span::call_site()) {
+            Ok(values) => quote! { #(#values),* }.to_string(),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn supplies_args_to_parameters_without_fixture() {
+        let item: ItemFn = parse_quote! { fn t(#[fixture] db: TempDb, n: usize, #[fixture] dir: Dir, m: usize) {} };
+        assert_eq!(
+            render(item, parse_quote!(1, 2), TestCaseDefaults::default()),
+            quote! { db(), 1, dir(), 2 }.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_fixture_with_pattern() {
+        let item: ItemFn = parse_quote! { fn t(n: usize, #[fixture] (a, b): (u8, u8)) {} };
+        assert_eq!(
+            render(item, parse_quote!(1), TestCaseDefaults::default()),
+            "fixture parameter has to be a plain identifier naming function which creates it"
+        );
+    }
+
+    #[test]
+    fn maps_keyword_args_and_defaults_by_name() {
+        let item: ItemFn =
+            parse_quote! { fn t(width: u32, height: u32, depth: u32, #[fixture] db: Db) {} };
+        assert_eq!(
+            render(
+                item.clone(),
+                parse_quote!(depth = 3, width = 10),
+                parse_quote!(height = 1)
+            ),
+            quote! { 10, 1, 3, db() }.to_string()
+        );
+        assert_eq!(
+            render(
+                item,
+                parse_quote!(10, depth = 3, height = 2),
+                parse_quote!(height = 1)
+            ),
+            quote! { 10, 2, 3, db() }.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keyword_args() {
+        let item: ItemFn = parse_quote! { fn t(width: u32, height: u32) {} };
+        let none = TestCaseDefaults::default;
+        assert_eq!(
+            render(item.clone(), parse_quote!(width = 1, heigth = 2), none()),
+            "test function has no parameter `heigth`"
+        );
+        assert_eq!(
+            render(item.clone(), parse_quote!(1, 2), parse_quote!(depth = 1)),
+            "test function has no parameter `depth`"
+        );
+        assert_eq!(
+            render(item.clone(), parse_quote!(width = 1, width = 2), none()),
+            "parameter `width` is given more than once"
+        );
+        assert_eq!(
+            render(item.clone(), parse_quote!(1, width = 2), none()),
+            "parameter `width` is already given positionally"
+        );
+        assert_eq!(
+            render(item.clone(), parse_quote!(width = 1, 2), none()),
+            "positional arguments have to precede keyword arguments"
+        );
+        assert_eq!(
+            render(item, parse_quote!(width = 1), none()),
+            "missing value of parameter `height`, give it as `height = ...` or declare it in `#[test_case_defaults(...)]`"
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_argument_count() {
+        let item: ItemFn = parse_quote! { fn t(a: u32, #[fixture] db: Db, b: u32, c: u32) {} };
+        let none = TestCaseDefaults::default;
+        assert_eq!(
+            render(item.clone(), parse_quote!(1, 2), none()),
+            "test case has 2 arguments, but test function expects 3: missing value of parameter `c`"
+        );
+        assert_eq!(
+            render(item.clone(), parse_quote!(1), none()),
+            "test case has 1 argument, but test function expects 3: missing values of parameters `b`, `c`"
+        );
+        assert_eq!(
+            render(item, parse_quote!(1, 2, 3, 4 + 1), none()),
+            "test case has 4 arguments, but test function expects 3: argument `4 + 1` has no parameter"
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_axis_count() {
+        let item: ItemFn = parse_quote! { fn t(a: u32) {} };
+        let args = parse_quote!(1, 2, 3);
+        let defaults = TestCaseDefaults::default();
+        let source = ArgumentsSource::TestMatrix;
+        let error =
+            call_arguments(&item, &args, &defaults, false, source, Span::call_site()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "test matrix has 3 axes, but test function expects 1: axes 2, 3 have no parameter"
+        );
+    }
+}
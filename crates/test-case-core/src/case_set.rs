@@ -0,0 +1,168 @@
+use crate::{TestCase, TokenStream2};
+use proc_macro2::TokenTree;
+use quote::{quote, ToTokens};
+use std::iter;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Paren;
+use syn::{bracketed, parenthesized, Expr, Ident, Token};
+
+/// Sets of test cases declared with `case_set!(tricky_inputs: ["", "ünïcödé" ; "unicode"])`.
+#[derive(Debug)]
+pub struct CaseSets(Vec<CaseSet>);
+
+#[derive(Debug)]
+struct CaseSet {
+    name: Ident,
+    /// Tokens of every case, exactly as they would be written inside `#[test_case(...)]`.
+    cases: Vec<TokenStream2>,
+}
+
+/// Tokens of a single case, validated to be a correct test case.
+///
+/// Case with one argument is written as is (`"x" => 1 ; "comment"`) and ends at the next top-level
+/// `,`. Case wrapped in parentheses as a whole (`(1, 2 => 3)`) holds complete `#[test_case(...)]`
+/// content, so it can take several arguments; tuple argument has to be wrapped twice (`((1, 2))`).
+struct CaseTokens(TokenStream2);
+
+impl Parse for CaseTokens {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let tokens = if input.peek(Paren) && is_whole_case(input) {
+            let content;
+            parenthesized!(content in input);
+            content.parse()?
+        } else {
+            // Argument is parsed as expression, so that commas of generics or closures don't end
+            // the case early.
+            let argument: Expr = input.parse()?;
+            let mut tokens = argument.into_token_stream();
+            while !input.is_empty() && !input.peek(Token![,]) {
+                tokens.extend(iter::once(input.parse::<TokenTree>()?));
+            }
+            tokens
+        };
+        syn::parse2::<TestCase>(tokens.clone())?;
+        Ok(Self(tokens))
+    }
+}
+
+/// Whether the next token tree is followed by the end of the case.
+fn is_whole_case(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<TokenTree>().is_ok() && (fork.is_empty() || fork.peek(Token![,]))
+}
+
+impl Parse for CaseSet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let _: Token![:] = input.parse()?;
+        let content;
+        bracketed!(content in input);
+        let cases = Punctuated::<CaseTokens, Token![,]>::parse_terminated(&content)?;
+
+        Ok(Self {
+            name,
+            cases: cases.into_iter().map(|case| case.0).collect(),
+        })
+    }
+}
+
+impl Parse for CaseSets {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut sets = vec![];
+        while !input.is_empty() {
+            sets.push(input.parse()?);
+            if !input.is_empty() {
+                let _: Token![;] = input.parse()?;
+            }
+        }
+
+        Ok(Self(sets))
+    }
+}
+
+impl CaseSets {
+    /// Renders every set as `macro_rules!` callback which prefixes given item with `#[test_case(...)]`
+    /// attribute per case, `#[test_case_set(name)]` invokes it with the test function.
+    pub fn render(&self) -> TokenStream2 {
+        self.0
+            .iter()
+            .map(|set| {
+                let name = &set.name;
+                let cases = &set.cases;
+                quote! {
+                    #[allow(unused_macros)]
+                    macro_rules! #name {
+                        ($($item:tt)*) => {
+                            #(#[test_case::test_case(#cases)])*
+                            $($item)*
+                        };
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::case_set::CaseSets;
+
+    #[test]
+    fn parses_multiple_sets() {
+        let sets: CaseSets = syn::parse_quote! {
+            tricky_inputs: [(""), ("\0"), ("ünïcödé" ; "unicode")];
+            numbers: [(1, 2 => 3), (-1, 1 => 0 ; "opposite")]
+        };
+        let cases: Vec<(String, usize)> = sets
+            .0
+            .iter()
+            .map(|set| (set.name.to_string(), set.cases.len()))
+            .collect();
+        assert_eq!(
+            cases,
+            [("tricky_inputs".to_string(), 3), ("numbers".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn parses_cases_without_parentheses() {
+        let sets: CaseSets = syn::parse_quote! {
+            tricky_inputs: ["", "\0", "ünïcödé" ; "unicode"];
+            mixed: [
+                HashMap::<u8, u8>::new() => it empty,
+                (1, 2 => 3),
+                ((1, 2)) ; "tuple",
+                |a, b| a + b => ignore ; "closure",
+                width = 10 => 20,
+            ]
+        };
+        let cases: Vec<Vec<String>> = sets
+            .0
+            .iter()
+            .map(|set| set.cases.iter().map(ToString::to_string).collect())
+            .collect();
+        assert_eq!(
+            cases,
+            [
+                vec![r#""""#, r#""\0""#, r#""ünïcödé" ; "unicode""#],
+                vec![
+                    "HashMap :: < u8 , u8 > :: new () => it empty",
+                    "1 , 2 => 3",
+                    r#"((1 , 2)) ; "tuple""#,
+                    r#"| a , b | a + b => ignore ; "closure""#,
+                    "width = 10 => 20",
+                ]
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_case() {
+        let error = syn::parse_str::<CaseSets>("inputs: [(1 => )]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unexpected end of input, expected an expression"
+        );
+    }
+}
@@ -1,42 +1,61 @@
-use crate::utils::fmt_syn;
+use crate::suggestions::misspelled_keyword;
+use crate::utils::{fmt_syn, span_of};
 use proc_macro2::Group;
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
-use quote::{quote, TokenStreamExt};
+use quote::{quote, quote_spanned, TokenStreamExt};
 use std::fmt::{Display, Formatter};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse_quote, Expr};
+use syn::{parse_quote, Expr, Token};
 
 mod kw {
-    syn::custom_keyword!(eq);
-    syn::custom_keyword!(equal_to);
-    syn::custom_keyword!(lt);
-    syn::custom_keyword!(less_than);
-    syn::custom_keyword!(gt);
-    syn::custom_keyword!(greater_than);
-    syn::custom_keyword!(leq);
-    syn::custom_keyword!(less_or_equal_than);
-    syn::custom_keyword!(geq);
-    syn::custom_keyword!(greater_or_equal_than);
-    syn::custom_keyword!(almost);
-    syn::custom_keyword!(almost_equal_to);
-    syn::custom_keyword!(precision);
-    syn::custom_keyword!(existing_path);
-    syn::custom_keyword!(directory);
-    syn::custom_keyword!(dir);
-    syn::custom_keyword!(file);
-    syn::custom_keyword!(contains);
-    syn::custom_keyword!(contains_in_order);
-    syn::custom_keyword!(not);
-    syn::custom_keyword!(and);
-    syn::custom_keyword!(or);
-    syn::custom_keyword!(len);
-    syn::custom_keyword!(has_length);
-    syn::custom_keyword!(count);
-    syn::custom_keyword!(has_count);
-    syn::custom_keyword!(empty);
-    syn::custom_keyword!(matching_regex);
-    syn::custom_keyword!(matches_regex);
+    crate::suggestions::keywords!(
+        eq,
+        equal_to,
+        lt,
+        less_than,
+        gt,
+        greater_than,
+        leq,
+        less_or_equal_than,
+        geq,
+        greater_or_equal_than,
+        almost,
+        almost_equal_to,
+        precision,
+        existing_path,
+        directory,
+        dir,
+        file,
+        symlink,
+        readonly,
+        with_content,
+        with_len,
+        containing,
+        contains,
+        contains_in_order,
+        contains_subsequence,
+        contains_all,
+        contains_any,
+        subset_of,
+        superset_of,
+        has_key,
+        has_entry,
+        keys,
+        satisfies,
+        not,
+        and,
+        or,
+        len,
+        has_length,
+        count,
+        has_count,
+        empty,
+        matching_regex,
+        matches_regex,
+        json_eq,
+        json_subset,
+    );
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -53,6 +72,23 @@ pub enum PathToken {
     Any,
     Dir,
     File,
+    Symlink,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathCheck {
+    WithContent(Box<Expr>),
+    WithLen(Box<Expr>),
+    Containing(Box<Expr>),
+    Readonly,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SetToken {
+    SubsetOf,
+    SupersetOf,
+    ContainsAll,
+    ContainsAny,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -70,6 +106,7 @@ pub struct AlmostEqual {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Path {
     pub token: PathToken,
+    pub checks: Vec<PathCheck>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -82,6 +119,38 @@ pub struct ContainsInOrder {
     pub expected_slice: Box<Expr>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainsSubsequence {
+    pub expected_subsequence: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetRelation {
+    pub token: SetToken,
+    pub expected_set: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HasKey {
+    pub expected_key: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HasEntry {
+    pub expected_key: Box<Expr>,
+    pub expected_value: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeysEq {
+    pub expected_keys: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Satisfies {
+    pub matcher: Box<Expr>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Len {
     pub expected_len: Box<Expr>,
@@ -98,6 +167,20 @@ pub struct Regex {
     pub expected_regex: Box<Expr>,
 }
 
+#[cfg(feature = "with-json")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonToken {
+    Eq,
+    Subset,
+}
+
+#[cfg(feature = "with-json")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Json {
+    pub token: JsonToken,
+    pub expected_json: Box<Expr>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ComplexTestCase {
     Not(Box<ComplexTestCase>),
@@ -108,11 +191,19 @@ pub enum ComplexTestCase {
     Path(Path),
     Contains(Contains),
     ContainsInOrder(ContainsInOrder),
+    ContainsSubsequence(ContainsSubsequence),
+    SetRelation(SetRelation),
+    HasKey(HasKey),
+    HasEntry(HasEntry),
+    KeysEq(KeysEq),
+    Satisfies(Satisfies),
     Len(Len),
     Count(Count),
     Empty,
     #[cfg(feature = "with-regex")]
     Regex(Regex),
+    #[cfg(feature = "with-json")]
+    Json(Json),
 }
 
 impl Parse for ComplexTestCase {
@@ -147,6 +238,39 @@ impl Display for PathToken {
             PathToken::Any => f.write_str("path"),
             PathToken::Dir => f.write_str("dir"),
             PathToken::File => f.write_str("file"),
+            PathToken::Symlink => f.write_str("symlink"),
+        }
+    }
+}
+
+impl Display for PathCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathCheck::WithContent(expected) => write!(f, "with_content {}", fmt_syn(expected)),
+            PathCheck::WithLen(expected) => write!(f, "with_len {}", fmt_syn(expected)),
+            PathCheck::Containing(expected) => write!(f, "containing {}", fmt_syn(expected)),
+            PathCheck::Readonly => f.write_str("readonly"),
+        }
+    }
+}
+
+#[cfg(feature = "with-json")]
+impl Display for JsonToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonToken::Eq => f.write_str("json_eq"),
+            JsonToken::Subset => f.write_str("json_subset"),
+        }
+    }
+}
+
+impl Display for SetToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetToken::SubsetOf => f.write_str("subset_of"),
+            SetToken::SupersetOf => f.write_str("superset_of"),
+            SetToken::ContainsAll => f.write_str("contains_all"),
+            SetToken::ContainsAny => f.write_str("contains_any"),
         }
     }
 }
@@ -154,18 +278,23 @@ impl Display for PathToken {
 impl Display for ComplexTestCase {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            ComplexTestCase::Not(not) => write!(f, "not {not}"),
+            ComplexTestCase::Not(not) => {
+                f.write_str("not ")?;
+                fmt_operand(not, f)
+            }
             ComplexTestCase::And(cases) => {
-                write!(f, "{}", cases[0])?;
+                fmt_operand(&cases[0], f)?;
                 for case in cases[1..].iter() {
-                    write!(f, " and {case}")?;
+                    f.write_str(" and ")?;
+                    fmt_operand(case, f)?;
                 }
                 Ok(())
             }
             ComplexTestCase::Or(cases) => {
-                write!(f, "{}", cases[0])?;
+                fmt_operand(&cases[0], f)?;
                 for case in cases[1..].iter() {
-                    write!(f, " or {case}")?;
+                    f.write_str(" or ")?;
+                    fmt_operand(case, f)?;
                 }
                 Ok(())
             }
@@ -182,13 +311,44 @@ impl Display for ComplexTestCase {
                 fmt_syn(expected_value),
                 fmt_syn(precision)
             ),
-            ComplexTestCase::Path(Path { token }) => write!(f, "path {token}"),
+            ComplexTestCase::Path(Path { token, checks }) => {
+                write!(f, "path {token}")?;
+                for check in checks {
+                    write!(f, " {check}")?;
+                }
+                Ok(())
+            }
             ComplexTestCase::Contains(Contains { expected_element }) => {
                 write!(f, "contains {}", fmt_syn(expected_element))
             }
             ComplexTestCase::ContainsInOrder(ContainsInOrder { expected_slice }) => {
                 write!(f, "contains in order {}", fmt_syn(expected_slice))
             }
+            ComplexTestCase::ContainsSubsequence(ContainsSubsequence {
+                expected_subsequence,
+            }) => write!(f, "contains subsequence {}", fmt_syn(expected_subsequence)),
+            ComplexTestCase::SetRelation(SetRelation {
+                token,
+                expected_set,
+            }) => write!(f, "{} {}", token, fmt_syn(expected_set)),
+            ComplexTestCase::HasKey(HasKey { expected_key }) => {
+                write!(f, "has_key {}", fmt_syn(expected_key))
+            }
+            ComplexTestCase::HasEntry(HasEntry {
+                expected_key,
+                expected_value,
+            }) => write!(
+                f,
+                "has_entry {} => {}",
+                fmt_syn(expected_key),
+                fmt_syn(expected_value)
+            ),
+            ComplexTestCase::KeysEq(KeysEq { expected_keys }) => {
+                write!(f, "keys eq {}", fmt_syn(expected_keys))
+            }
+            ComplexTestCase::Satisfies(Satisfies { matcher }) => {
+                write!(f, "satisfies {}", fmt_syn(matcher))
+            }
             ComplexTestCase::Len(Len { expected_len }) => {
                 write!(f, "len {}", fmt_syn(expected_len))
             }
@@ -202,15 +362,128 @@ impl Display for ComplexTestCase {
             ComplexTestCase::Regex(Regex { expected_regex }) => {
                 write!(f, "regex {}", fmt_syn(expected_regex))
             }
+            #[cfg(feature = "with-json")]
+            ComplexTestCase::Json(Json {
+                token,
+                expected_json,
+            }) => write!(f, "{} {}", token, fmt_syn(expected_json)),
+        }
+    }
+}
+
+/// Alternate form (`{:#}`) wraps nested `and`/`or` groups in parentheses, default form doesn't
+/// since it's used to generate test names.
+fn fmt_operand(case: &ComplexTestCase, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match case {
+        ComplexTestCase::And(_) | ComplexTestCase::Or(_) if f.alternate() => {
+            write!(f, "({case:#})")
         }
+        _ if f.alternate() => write!(f, "{case:#}"),
+        _ => write!(f, "{case}"),
     }
 }
 
 impl ComplexTestCase {
     pub fn assertion(&self) -> TokenStream {
-        let tokens = self.boolean_check();
+        let check = self.check();
+        let description = format!("{self:#}");
+
+        // `count` moves `_result` into the iterator, so there is nothing left to print afterwards.
+        let report = if self.consumes_result() {
+            quote! {
+                panic!("assertion `{}` failed\n{}", #description, _tc_failures)
+            }
+        } else {
+            quote! {
+                let _tc_result_debug = {
+                    #[allow(unused_imports)]
+                    use ::test_case::internal::debug::{ViaDebug as _, ViaNoDebug as _};
+                    (&::test_case::internal::debug::DebugProbe(&_result)).debug_string()
+                };
+                panic!(
+                    "assertion `{}` failed\n{}\nresult: {}",
+                    #description, _tc_failures, _tc_result_debug
+                )
+            }
+        };
+
+        quote! {
+            if let ::core::result::Result::Err(_tc_failures) = #check {
+                #report
+            }
+        }
+    }
+
+    fn consumes_result(&self) -> bool {
+        match self {
+            ComplexTestCase::Count(_) => true,
+            ComplexTestCase::Not(not) => not.consumes_result(),
+            ComplexTestCase::And(cases) | ComplexTestCase::Or(cases) => {
+                cases.iter().any(ComplexTestCase::consumes_result)
+            }
+            _ => false,
+        }
+    }
+
+    /// Expression evaluating to `Result<(), String>`, where error lists every sub-assertion which
+    /// caused the whole assertion to fail.
+    fn check(&self) -> TokenStream {
+        match self {
+            ComplexTestCase::And(cases) => and_check(cases),
+            ComplexTestCase::Or(cases) => or_check(cases),
+            _ => {
+                let tokens = self.boolean_check();
+                let description = format!("{self:#}");
+                let message = if let Some(details) = self.failure_details() {
+                    quote! { format!("  - `{}` failed: {}", #description, #details) }
+                } else {
+                    quote! { format!("  - `{}` failed", #description) }
+                };
 
-        quote! { assert!(#tokens) }
+                quote! {
+                    if #tokens {
+                        ::core::result::Result::Ok(())
+                    } else {
+                        ::core::result::Result::Err(#message)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Expression evaluating to `Display` value explaining why the assertion failed, for
+    /// assertions which can tell more than `assertion failed`.
+    fn failure_details(&self) -> Option<TokenStream> {
+        match self {
+            ComplexTestCase::ContainsInOrder(ContainsInOrder { expected_slice }) => {
+                Some(sequence_report(quote! { Contiguous }, expected_slice))
+            }
+            ComplexTestCase::ContainsSubsequence(ContainsSubsequence {
+                expected_subsequence,
+            }) => Some(sequence_report(quote! { WithGaps }, expected_subsequence)),
+            ComplexTestCase::SetRelation(SetRelation {
+                token,
+                expected_set,
+            }) => Some(set_relation_report(token, expected_set)),
+            ComplexTestCase::HasKey(HasKey { expected_key }) => {
+                Some(missing_key_details(expected_key))
+            }
+            ComplexTestCase::HasEntry(HasEntry {
+                expected_key,
+                expected_value,
+            }) => Some(has_entry_details(expected_key, expected_value)),
+            ComplexTestCase::KeysEq(KeysEq { expected_keys }) => {
+                Some(keys_eq_report(expected_keys))
+            }
+            ComplexTestCase::Path(Path { token, checks }) => Some(path_report(token, checks)),
+            ComplexTestCase::Satisfies(Satisfies { matcher }) => Some(satisfies_details(matcher)),
+            #[cfg(feature = "with-json")]
+            ComplexTestCase::Json(Json {
+                token,
+                expected_json,
+            }) => Some(json_report(token, expected_json)),
+            _ => None,
+        }
     }
 
     fn boolean_check(&self) -> TokenStream {
@@ -226,18 +499,37 @@ impl ComplexTestCase {
                 expected_value,
                 precision,
             }) => almost_equal_assertion(expected_value, precision),
-            ComplexTestCase::Path(Path { token }) => path_assertion(token),
+            ComplexTestCase::Path(Path { token, checks }) => path_assertion(token, checks),
             ComplexTestCase::Contains(Contains { expected_element }) => {
                 contains_assertion(expected_element)
             }
             ComplexTestCase::ContainsInOrder(ContainsInOrder { expected_slice }) => {
                 contains_in_order_assertion(expected_slice)
             }
+            ComplexTestCase::ContainsSubsequence(ContainsSubsequence {
+                expected_subsequence,
+            }) => contains_subsequence_assertion(expected_subsequence),
+            ComplexTestCase::SetRelation(SetRelation {
+                token,
+                expected_set,
+            }) => set_relation_assertion(token, expected_set),
+            ComplexTestCase::HasKey(HasKey { expected_key }) => has_key_assertion(expected_key),
+            ComplexTestCase::HasEntry(HasEntry {
+                expected_key,
+                expected_value,
+            }) => has_entry_assertion(expected_key, expected_value),
+            ComplexTestCase::KeysEq(KeysEq { expected_keys }) => keys_eq_assertion(expected_keys),
+            ComplexTestCase::Satisfies(Satisfies { matcher }) => satisfies_assertion(matcher),
             ComplexTestCase::Len(Len { expected_len }) => len_assertion(expected_len),
             ComplexTestCase::Count(Count { expected_len }) => count_assertion(expected_len),
             ComplexTestCase::Empty => empty_assertion(),
             #[cfg(feature = "with-regex")]
             ComplexTestCase::Regex(Regex { expected_regex }) => regex_assertion(expected_regex),
+            #[cfg(feature = "with-json")]
+            ComplexTestCase::Json(Json {
+                token,
+                expected_json,
+            }) => json_assertion(token, expected_json),
         }
     }
 
@@ -284,17 +576,15 @@ impl ComplexTestCase {
                 precision,
             })
         } else if input.parse::<kw::existing_path>().is_ok() {
-            ComplexTestCase::Path(Path {
-                token: PathToken::Any,
-            })
+            ComplexTestCase::Path(Path::parse_checks(PathToken::Any, input)?)
         } else if input.parse::<kw::directory>().is_ok() || input.parse::<kw::dir>().is_ok() {
-            ComplexTestCase::Path(Path {
-                token: PathToken::Dir,
-            })
+            ComplexTestCase::Path(Path::parse_checks(PathToken::Dir, input)?)
         } else if input.parse::<kw::file>().is_ok() {
-            ComplexTestCase::Path(Path {
-                token: PathToken::File,
-            })
+            ComplexTestCase::Path(Path::parse_checks(PathToken::File, input)?)
+        } else if input.parse::<kw::symlink>().is_ok() {
+            ComplexTestCase::Path(Path::parse_checks(PathToken::Symlink, input)?)
+        } else if input.peek(kw::readonly) {
+            ComplexTestCase::Path(Path::parse_checks(PathToken::Any, input)?)
         } else if input.parse::<kw::contains>().is_ok() {
             ComplexTestCase::Contains(Contains {
                 expected_element: input.parse()?,
@@ -303,6 +593,36 @@ impl ComplexTestCase {
             ComplexTestCase::ContainsInOrder(ContainsInOrder {
                 expected_slice: input.parse()?,
             })
+        } else if input.parse::<kw::contains_subsequence>().is_ok() {
+            ComplexTestCase::ContainsSubsequence(ContainsSubsequence {
+                expected_subsequence: input.parse()?,
+            })
+        } else if let Some(token) = SetToken::parse_opt(input) {
+            ComplexTestCase::SetRelation(SetRelation {
+                token,
+                expected_set: input.parse()?,
+            })
+        } else if input.parse::<kw::has_key>().is_ok() {
+            ComplexTestCase::HasKey(HasKey {
+                expected_key: input.parse()?,
+            })
+        } else if input.parse::<kw::has_entry>().is_ok() {
+            let expected_key = input.parse()?;
+            let _ = input.parse::<Token![=>]>()?;
+            let expected_value = input.parse()?;
+            ComplexTestCase::HasEntry(HasEntry {
+                expected_key,
+                expected_value,
+            })
+        } else if input.parse::<kw::keys>().is_ok() {
+            let _ = input.parse::<kw::eq>()?;
+            ComplexTestCase::KeysEq(KeysEq {
+                expected_keys: input.parse()?,
+            })
+        } else if input.parse::<kw::satisfies>().is_ok() {
+            ComplexTestCase::Satisfies(Satisfies {
+                matcher: input.parse()?,
+            })
         } else if input.parse::<kw::not>().is_ok() {
             ComplexTestCase::Not(Box::new(input.parse()?))
         } else if input.parse::<kw::len>().is_ok() || input.parse::<kw::has_length>().is_ok() {
@@ -327,19 +647,136 @@ impl ComplexTestCase {
                     return Err(input.error("'with-regex' feature is required to use 'matches-regex' keyword"));
                 }
             }
+        } else if input.peek(kw::json_eq) || input.peek(kw::json_subset) {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "with-json")] {
+                    let token = if input.parse::<kw::json_eq>().is_ok() {
+                        JsonToken::Eq
+                    } else {
+                        let _ = input.parse::<kw::json_subset>()?;
+                        JsonToken::Subset
+                    };
+                    ComplexTestCase::Json(Json {
+                        token,
+                        expected_json: input.parse()?,
+                    })
+                } else {
+                    return Err(input.error("'with-json' feature is required to use 'json_eq' and 'json_subset' keywords"));
+                }
+            }
+        } else if let Some(error) = misspelled_keyword(input, kw::ALL.iter().copied()) {
+            return Err(error);
         } else {
             return Err(input.error("cannot parse complex expression"));
         })
     }
 }
 
+impl Path {
+    /// Parses sub-assertions following path token, e.g. `file with_len 42 readonly`.
+    fn parse_checks(token: PathToken, input: ParseStream) -> syn::Result<Self> {
+        let mut checks = vec![];
+        loop {
+            let check = if input.peek(kw::with_content) {
+                expect_path_token::<kw::with_content>(input, &token, PathToken::File)?;
+                PathCheck::WithContent(input.parse()?)
+            } else if input.peek(kw::with_len) {
+                expect_path_token::<kw::with_len>(input, &token, PathToken::File)?;
+                PathCheck::WithLen(input.parse()?)
+            } else if input.peek(kw::containing) {
+                expect_path_token::<kw::containing>(input, &token, PathToken::Dir)?;
+                PathCheck::Containing(input.parse()?)
+            } else if input.parse::<kw::readonly>().is_ok() {
+                PathCheck::Readonly
+            } else {
+                break;
+            };
+            checks.push(check);
+        }
+
+        Ok(Path { token, checks })
+    }
+}
+
+fn expect_path_token<Keyword: Parse + syn::token::Token>(
+    input: ParseStream,
+    actual: &PathToken,
+    expected: PathToken,
+) -> syn::Result<()> {
+    let span = input.span();
+    let _ = input.parse::<Keyword>()?;
+    if *actual == expected {
+        Ok(())
+    } else {
+        let msg = format!(
+            "{} can only be used after '{}'",
+            Keyword::display(),
+            expected
+        );
+        Err(syn::Error::new(span, msg))
+    }
+}
+
+impl SetToken {
+    fn parse_opt(input: ParseStream) -> Option<Self> {
+        if input.parse::<kw::subset_of>().is_ok() {
+            Some(SetToken::SubsetOf)
+        } else if input.parse::<kw::superset_of>().is_ok() {
+            Some(SetToken::SupersetOf)
+        } else if input.parse::<kw::contains_all>().is_ok() {
+            Some(SetToken::ContainsAll)
+        } else if input.parse::<kw::contains_any>().is_ok() {
+            Some(SetToken::ContainsAny)
+        } else {
+            None
+        }
+    }
+}
+
+fn and_check(cases: &[ComplexTestCase]) -> TokenStream {
+    let mut ts = quote! { ::core::result::Result::Ok(()) };
+
+    for case in cases.iter().rev() {
+        let case = case.check();
+        ts = quote! {
+            match #case {
+                ::core::result::Result::Ok(()) => #ts,
+                ::core::result::Result::Err(_tc_failure) => ::core::result::Result::Err(_tc_failure),
+            }
+        };
+    }
+
+    ts
+}
+
+fn or_check(cases: &[ComplexTestCase]) -> TokenStream {
+    let mut ts = cases[cases.len() - 1].check();
+
+    for case in cases.iter().rev().skip(1) {
+        let case = case.check();
+        ts = quote! {
+            match #case {
+                ::core::result::Result::Ok(()) => ::core::result::Result::Ok(()),
+                ::core::result::Result::Err(_tc_failure) => match #ts {
+                    ::core::result::Result::Ok(()) => ::core::result::Result::Ok(()),
+                    ::core::result::Result::Err(_tc_other_failures) => ::core::result::Result::Err(
+                        format!("{}\n{}", _tc_failure, _tc_other_failures),
+                    ),
+                },
+            }
+        };
+    }
+
+    ts
+}
+
 fn and_assertion(cases: &[ComplexTestCase]) -> TokenStream {
     let ts = cases[0].boolean_check();
-    let mut ts: TokenStream = parse_quote! { #ts };
+    let mut ts: TokenStream = parse_quote! { (#ts) };
 
     for case in cases.iter().skip(1) {
         let case = case.boolean_check();
-        let case: TokenStream = parse_quote! { && #case };
+        let case: TokenStream = parse_quote! { && (#case) };
         ts.append_all(case);
     }
 
@@ -348,11 +785,11 @@ fn and_assertion(cases: &[ComplexTestCase]) -> TokenStream {
 
 fn or_assertion(cases: &[ComplexTestCase]) -> TokenStream {
     let ts = cases[0].boolean_check();
-    let mut ts: TokenStream = parse_quote! { #ts };
+    let mut ts: TokenStream = parse_quote! { (#ts) };
 
     for case in cases.iter().skip(1) {
         let case = case.boolean_check();
-        let case: TokenStream = parse_quote! { || #case };
+        let case: TokenStream = parse_quote! { || (#case) };
         ts.append_all(case);
     }
 
@@ -376,58 +813,224 @@ fn negate(tokens: TokenStream) -> TokenStream {
     }
 }
 
+fn sequence_report(kind: TokenStream, expected_sequence: &Expr) -> TokenStream {
+    quote_spanned! {span_of(expected_sequence)=>
+        {
+            #[allow(unused_imports)]
+            use ::test_case::internal::sequence::{
+                DerefSequence as _, IterSequence as _, StrSequence as _,
+            };
+            use ::test_case::internal::sequence::{Haystack, Kind};
+
+            let _tc_expected_sequence = #expected_sequence;
+            let _tc_report = (&&Haystack(&_result))
+                .sequence_report(&_tc_expected_sequence, Kind::#kind);
+            _tc_report
+        }
+    }
+}
+
 fn contains_in_order_assertion(expected_slice: &Expr) -> TokenStream {
-    parse_quote! {
+    let report = sequence_report(quote! { Contiguous }, expected_slice);
+
+    quote! { #report.holds() }
+}
+
+fn contains_subsequence_assertion(expected_subsequence: &Expr) -> TokenStream {
+    let report = sequence_report(quote! { WithGaps }, expected_subsequence);
+
+    quote! { #report.holds() }
+}
+
+fn set_relation_report(token: &SetToken, expected_set: &Expr) -> TokenStream {
+    let relation = match token {
+        SetToken::SubsetOf => quote! { SubsetOf },
+        SetToken::SupersetOf => quote! { SupersetOf },
+        SetToken::ContainsAll => quote! { ContainsAll },
+        SetToken::ContainsAny => quote! { ContainsAny },
+    };
+
+    relation_report(relation, quote! { &_result }, expected_set)
+}
+
+fn relation_report(relation: TokenStream, actual: TokenStream, expected_set: &Expr) -> TokenStream {
+    quote_spanned! {span_of(expected_set)=>
         {
-            let mut _tc_outcome = false;
-            for i in 0..=_result.len() - #expected_slice.len() {
-                if #expected_slice == _result[i..i+#expected_slice.len()] {
-                    _tc_outcome = true;
-                }
-            }
-            _tc_outcome
+            #[allow(unused_imports)]
+            use ::test_case::internal::set_relation::{HashedMembership as _, LinearMembership as _};
+            use ::test_case::internal::set_relation::{Collection, Relation, Report};
+
+            let _tc_expected_set = #expected_set;
+            let _tc_actual = Collection::new(#actual);
+            let _tc_expected = _tc_actual.with_items(&_tc_expected_set);
+            let _tc_actual_membership = (&_tc_actual).membership();
+            let _tc_expected_membership = (&_tc_expected).membership();
+            let _tc_report = Report::new(
+                Relation::#relation,
+                &_tc_actual,
+                &_tc_actual_membership,
+                &_tc_expected,
+                &_tc_expected_membership,
+            );
+            _tc_report
         }
     }
 }
 
+fn set_relation_assertion(token: &SetToken, expected_set: &Expr) -> TokenStream {
+    let report = set_relation_report(token, expected_set);
+
+    quote! { #report.holds() }
+}
+
+fn map_get(expected_key: &Expr) -> TokenStream {
+    quote_spanned! {span_of(expected_key)=>
+        ({
+            #[allow(unused_imports)]
+            use ::test_case::internal::map::{
+                DerefIndexedLookup as _, IndexedLookup as _, LinearLookup as _,
+            };
+            (&&&::test_case::internal::map::Lookup::new(&_result, _result.iter(), &#expected_key))
+                .get()
+        })
+    }
+}
+
+fn has_key_assertion(expected_key: &Expr) -> TokenStream {
+    let get = map_get(expected_key);
+
+    quote! { #get.is_some() }
+}
+
+fn missing_key_details(expected_key: &Expr) -> TokenStream {
+    quote_spanned! {span_of(expected_key)=>
+        format!(
+            "key {:?} not found, available keys: {:?}",
+            &#expected_key,
+            _result.keys().collect::<Vec<_>>()
+        )
+    }
+}
+
+fn has_entry_assertion(expected_key: &Expr, expected_value: &Expr) -> TokenStream {
+    let get = map_get(expected_key);
+
+    quote_spanned! {span_of(expected_value)=> #get == Some(&#expected_value) }
+}
+
+fn has_entry_details(expected_key: &Expr, expected_value: &Expr) -> TokenStream {
+    let get = map_get(expected_key);
+    let missing_key = missing_key_details(expected_key);
+
+    quote_spanned! {span_of(expected_value)=>
+        match #get {
+            Some(_tc_value) => format!(
+                "key {:?} maps to {:?}, expected {:?}",
+                &#expected_key,
+                _tc_value,
+                &#expected_value
+            ),
+            None => #missing_key,
+        }
+    }
+}
+
+fn keys_eq_report(expected_keys: &Expr) -> TokenStream {
+    quote_spanned! {span_of(expected_keys)=>
+        ::test_case::internal::set_relation::Report::by_partial_eq(
+            ::test_case::internal::set_relation::Relation::SameElements,
+            _result.keys(),
+            &#expected_keys,
+        )
+    }
+}
+
+fn keys_eq_assertion(expected_keys: &Expr) -> TokenStream {
+    let report = keys_eq_report(expected_keys);
+
+    quote! { #report.holds() }
+}
+
+fn satisfies_assertion(matcher: &Expr) -> TokenStream {
+    quote_spanned! {span_of(matcher)=> ::test_case::Matcher::matches(&#matcher, &_result) }
+}
+
+fn satisfies_details(matcher: &Expr) -> TokenStream {
+    quote_spanned! {span_of(matcher)=>
+        format!("expected {}", ::test_case::Matcher::describe(&#matcher))
+    }
+}
+
 fn contains_assertion(expected_element: &Expr) -> TokenStream {
-    parse_quote! { _result.iter().find(|i| i.eq(&&#expected_element)).is_some() }
+    quote_spanned! {span_of(expected_element)=>
+        _result.iter().find(|i| i.eq(&&#expected_element)).is_some()
+    }
 }
 
-fn path_assertion(token: &PathToken) -> TokenStream {
-    match token {
-        PathToken::Any => parse_quote! { std::path::Path::new(&_result).exists() },
-        PathToken::Dir => parse_quote! { std::path::Path::new(&_result).is_dir() },
-        PathToken::File => parse_quote! { std::path::Path::new(&_result).is_file() },
+fn path_report(token: &PathToken, checks: &[PathCheck]) -> TokenStream {
+    let kind = match token {
+        PathToken::Any => quote! { Any },
+        PathToken::Dir => quote! { Dir },
+        PathToken::File => quote! { File },
+        PathToken::Symlink => quote! { Symlink },
+    };
+    let checks = checks.iter().map(|check| match check {
+        PathCheck::WithContent(expected) => {
+            quote_spanned! {span_of(expected)=> .with_content(&#expected) }
+        }
+        PathCheck::WithLen(expected) => {
+            quote_spanned! {span_of(expected)=> .with_len(#expected) }
+        }
+        PathCheck::Containing(expected) => {
+            quote_spanned! {span_of(expected)=> .containing(#expected) }
+        }
+        PathCheck::Readonly => quote! { .readonly() },
+    });
+
+    quote! {
+        ::test_case::internal::path::Report::new(
+            &_result,
+            ::test_case::internal::path::Kind::#kind,
+        )
+        #(#checks)*
     }
 }
 
+fn path_assertion(token: &PathToken, checks: &[PathCheck]) -> TokenStream {
+    let report = path_report(token, checks);
+
+    quote! { #report.holds() }
+}
+
 fn almost_equal_assertion(expected_value: &Expr, precision: &Expr) -> TokenStream {
-    quote! { (_result - #expected_value).abs() < #precision }
+    let difference = quote_spanned! {span_of(expected_value)=> (_result - #expected_value).abs() };
+
+    quote_spanned! {span_of(precision)=> #difference < #precision }
 }
 
 fn ord_assertion(token: &OrderingToken, expected_value: &Expr) -> TokenStream {
-    let ts: TokenStream = match token {
-        OrderingToken::Eq => parse_quote! { == },
-        OrderingToken::Lt => parse_quote! { < },
-        OrderingToken::Gt => parse_quote! { > },
-        OrderingToken::Leq => parse_quote! { <= },
-        OrderingToken::Geq => parse_quote! { >= },
+    let span = span_of(expected_value);
+    let ts = match token {
+        OrderingToken::Eq => quote_spanned! {span=> == },
+        OrderingToken::Lt => quote_spanned! {span=> < },
+        OrderingToken::Gt => quote_spanned! {span=> > },
+        OrderingToken::Leq => quote_spanned! {span=> <= },
+        OrderingToken::Geq => quote_spanned! {span=> >= },
     };
 
-    quote! {
+    quote_spanned! {span=>
         _result #ts #expected_value
     }
 }
 
 fn len_assertion(expected_len: &Expr) -> TokenStream {
-    quote! {
+    quote_spanned! {span_of(expected_len)=>
         _result.len() == #expected_len
     }
 }
 
 fn count_assertion(expected_len: &Expr) -> TokenStream {
-    quote! {
+    quote_spanned! {span_of(expected_len)=>
         std::iter::IntoIterator::into_iter(_result).count() == #expected_len
     }
 }
@@ -440,7 +1043,7 @@ fn empty_assertion() -> TokenStream {
 
 #[cfg(feature = "with-regex")]
 fn regex_assertion(expected_regex: &Expr) -> TokenStream {
-    quote! {
+    quote_spanned! {span_of(expected_regex)=>
         {
             let re = ::test_case::Regex::new(#expected_regex).expect("Regex::new");
             re.is_match(_result)
@@ -448,6 +1051,29 @@ fn regex_assertion(expected_regex: &Expr) -> TokenStream {
     }
 }
 
+#[cfg(feature = "with-json")]
+fn json_report(token: &JsonToken, expected_json: &Expr) -> TokenStream {
+    let mode = match token {
+        JsonToken::Eq => quote! { Eq },
+        JsonToken::Subset => quote! { Subset },
+    };
+
+    quote_spanned! {span_of(expected_json)=>
+        ::test_case::internal::json::Report::new(
+            &_result,
+            &#expected_json,
+            ::test_case::internal::json::Mode::#mode,
+        )
+    }
+}
+
+#[cfg(feature = "with-json")]
+fn json_assertion(token: &JsonToken, expected_json: &Expr) -> TokenStream {
+    let report = json_report(token, expected_json);
+
+    quote! { #report.holds() }
+}
+
 fn not_assertion(not: &ComplexTestCase) -> TokenStream {
     match not {
         ComplexTestCase::Not(_) => {
@@ -464,26 +1090,48 @@ fn not_assertion(not: &ComplexTestCase) -> TokenStream {
             expected_value,
             precision,
         }) => negate(almost_equal_assertion(expected_value, precision)),
-        ComplexTestCase::Path(Path { token }) => negate(path_assertion(token)),
+        ComplexTestCase::Path(Path { token, checks }) => negate(path_assertion(token, checks)),
         ComplexTestCase::Contains(Contains { expected_element }) => {
             negate(contains_assertion(expected_element))
         }
         ComplexTestCase::ContainsInOrder(ContainsInOrder { expected_slice }) => {
             negate(contains_in_order_assertion(expected_slice))
         }
+        ComplexTestCase::ContainsSubsequence(ContainsSubsequence {
+            expected_subsequence,
+        }) => negate(contains_subsequence_assertion(expected_subsequence)),
+        ComplexTestCase::SetRelation(SetRelation {
+            token,
+            expected_set,
+        }) => negate(set_relation_assertion(token, expected_set)),
+        ComplexTestCase::HasKey(HasKey { expected_key }) => negate(has_key_assertion(expected_key)),
+        ComplexTestCase::HasEntry(HasEntry {
+            expected_key,
+            expected_value,
+        }) => negate(has_entry_assertion(expected_key, expected_value)),
+        ComplexTestCase::KeysEq(KeysEq { expected_keys }) => {
+            negate(keys_eq_assertion(expected_keys))
+        }
+        ComplexTestCase::Satisfies(Satisfies { matcher }) => negate(satisfies_assertion(matcher)),
         ComplexTestCase::Len(Len { expected_len }) => negate(len_assertion(expected_len)),
         ComplexTestCase::Count(Count { expected_len }) => negate(count_assertion(expected_len)),
         ComplexTestCase::Empty => negate(empty_assertion()),
         #[cfg(feature = "with-regex")]
         ComplexTestCase::Regex(Regex { expected_regex }) => negate(regex_assertion(expected_regex)),
+        #[cfg(feature = "with-json")]
+        ComplexTestCase::Json(Json {
+            token,
+            expected_json,
+        }) => negate(json_assertion(token, expected_json)),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::complex_expr::{
-        AlmostEqual, ComplexTestCase, Contains, ContainsInOrder, Count, Len, OrderingToken, Path,
-        PathToken,
+        AlmostEqual, ComplexTestCase, Contains, ContainsInOrder, ContainsSubsequence, Count,
+        HasEntry, HasKey, KeysEq, Len, OrderingToken, Path, PathCheck, PathToken, Satisfies,
+        SetRelation, SetToken,
     };
     use syn::{parse_quote, LitFloat, LitInt, LitStr};
 
@@ -584,32 +1232,90 @@ mod tests {
         assert_eq!(
             actual,
             ComplexTestCase::Path(Path {
-                token: PathToken::Any
+                token: PathToken::Any,
+                checks: vec![]
             })
         );
         let actual: ComplexTestCase = parse_quote! { file };
         assert_eq!(
             actual,
             ComplexTestCase::Path(Path {
-                token: PathToken::File
+                token: PathToken::File,
+                checks: vec![]
             })
         );
         let actual: ComplexTestCase = parse_quote! { dir };
         assert_eq!(
             actual,
             ComplexTestCase::Path(Path {
-                token: PathToken::Dir
+                token: PathToken::Dir,
+                checks: vec![]
             })
         );
         let actual: ComplexTestCase = parse_quote! { directory };
         assert_eq!(
             actual,
             ComplexTestCase::Path(Path {
-                token: PathToken::Dir
+                token: PathToken::Dir,
+                checks: vec![]
             })
         );
     }
 
+    #[test]
+    fn parses_path_checks_token_stream() {
+        let actual: ComplexTestCase = parse_quote! { file with_content "abc" with_len 3 readonly };
+        assert_eq!(
+            actual,
+            ComplexTestCase::Path(Path {
+                token: PathToken::File,
+                checks: vec![
+                    PathCheck::WithContent(Box::new(parse_quote! { "abc" })),
+                    PathCheck::WithLen(Box::new(parse_quote! { 3 })),
+                    PathCheck::Readonly,
+                ]
+            })
+        );
+        let actual: ComplexTestCase = parse_quote! { dir containing ["a", "b"] };
+        assert_eq!(
+            actual,
+            ComplexTestCase::Path(Path {
+                token: PathToken::Dir,
+                checks: vec![PathCheck::Containing(Box::new(parse_quote! { ["a", "b"] }))]
+            })
+        );
+        let actual: ComplexTestCase = parse_quote! { symlink };
+        assert_eq!(
+            actual,
+            ComplexTestCase::Path(Path {
+                token: PathToken::Symlink,
+                checks: vec![]
+            })
+        );
+        let actual: ComplexTestCase = parse_quote! { readonly };
+        assert_eq!(
+            actual,
+            ComplexTestCase::Path(Path {
+                token: PathToken::Any,
+                checks: vec![PathCheck::Readonly]
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_path_checks_after_wrong_token() {
+        let error = syn::parse_str::<ComplexTestCase>("dir with_len 3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`with_len` can only be used after 'file'"
+        );
+        let error = syn::parse_str::<ComplexTestCase>("file containing [\"a\"]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`containing` can only be used after 'dir'"
+        );
+    }
+
     #[test]
     fn parses_contains_token_stream() {
         let actual: ComplexTestCase = parse_quote! { contains 1.0 };
@@ -646,6 +1352,79 @@ mod tests {
         )
     }
 
+    #[test]
+    fn parses_contains_subsequence_token_stream() {
+        let actual: ComplexTestCase = parse_quote! { contains_subsequence [1, 3] };
+        assert_eq!(
+            actual,
+            ComplexTestCase::ContainsSubsequence(ContainsSubsequence {
+                expected_subsequence: Box::new(parse_quote! { [1, 3] })
+            })
+        )
+    }
+
+    #[test]
+    fn parses_set_relation_token_stream() {
+        let cases: [(ComplexTestCase, SetToken); 4] = [
+            (parse_quote! { subset_of [1, 2, 3] }, SetToken::SubsetOf),
+            (parse_quote! { superset_of [1, 2, 3] }, SetToken::SupersetOf),
+            (
+                parse_quote! { contains_all [1, 2, 3] },
+                SetToken::ContainsAll,
+            ),
+            (
+                parse_quote! { contains_any [1, 2, 3] },
+                SetToken::ContainsAny,
+            ),
+        ];
+        for (actual, token) in cases {
+            assert_eq!(
+                actual,
+                ComplexTestCase::SetRelation(SetRelation {
+                    token,
+                    expected_set: Box::new(parse_quote! { [1, 2, 3] })
+                })
+            )
+        }
+    }
+
+    #[test]
+    fn parses_map_token_stream() {
+        let actual: ComplexTestCase = parse_quote! { has_key "a" };
+        assert_eq!(
+            actual,
+            ComplexTestCase::HasKey(HasKey {
+                expected_key: Box::new(parse_quote! { "a" })
+            })
+        );
+        let actual: ComplexTestCase = parse_quote! { has_entry "a" => 1 };
+        assert_eq!(
+            actual,
+            ComplexTestCase::HasEntry(HasEntry {
+                expected_key: Box::new(parse_quote! { "a" }),
+                expected_value: Box::new(parse_quote! { 1 })
+            })
+        );
+        let actual: ComplexTestCase = parse_quote! { keys eq ["a", "b"] };
+        assert_eq!(
+            actual,
+            ComplexTestCase::KeysEq(KeysEq {
+                expected_keys: Box::new(parse_quote! { ["a", "b"] })
+            })
+        );
+    }
+
+    #[test]
+    fn parses_satisfies_token_stream() {
+        let actual: ComplexTestCase = parse_quote! { satisfies valid_iban() };
+        assert_eq!(
+            actual,
+            ComplexTestCase::Satisfies(Satisfies {
+                matcher: Box::new(parse_quote! { valid_iban() })
+            })
+        );
+    }
+
     #[test]
     fn parses_len_token_stream() {
         let actual1: ComplexTestCase = parse_quote! { len 10 };
@@ -709,6 +1488,35 @@ mod tests {
         assert_ord!(actual, OrderingToken::Lt, 1.0)
     }
 
+    #[test]
+    fn alternate_display_groups_nested_logic() {
+        let actual: ComplexTestCase =
+            parse_quote! { (lt 0.0 or gt 1.0) and not (eq 2.0 or eq 3.0) };
+        assert_eq!(
+            actual.to_string(),
+            "lt 0.0 or gt 1.0 and not eq 2.0 or eq 3.0"
+        );
+        assert_eq!(
+            format!("{actual:#}"),
+            "(lt 0.0 or gt 1.0) and not (eq 2.0 or eq 3.0)"
+        );
+    }
+
+    #[test]
+    fn prints_result_only_when_assertion_keeps_it() {
+        let borrowing: ComplexTestCase = parse_quote! { len 3 and contains 1 };
+        assert!(borrowing
+            .assertion()
+            .to_string()
+            .contains("_tc_result_debug"));
+
+        let consuming: ComplexTestCase = parse_quote! { not count 3 };
+        assert!(!consuming
+            .assertion()
+            .to_string()
+            .contains("_tc_result_debug"));
+    }
+
     #[test]
     fn parses_logic() {
         let actual: ComplexTestCase = parse_quote! { lt 1.0 and gt 0.0 };
@@ -742,4 +1550,16 @@ mod tests {
             _ => panic!("test failed"),
         }
     }
+
+    #[test]
+    fn suggests_closest_keyword_for_misspelled_one() {
+        let error = syn::parse_str::<ComplexTestCase>("lenght 3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown keyword `lenght`, did you mean `len`?"
+        );
+
+        let error = syn::parse_str::<ComplexTestCase>("value 3").unwrap_err();
+        assert_eq!(error.to_string(), "cannot parse complex expression");
+    }
 }
@@ -0,0 +1,195 @@
+//! Grammar shared by `#[test_case(...)]` and `#[test_matrix(...)]` attributes:
+//!
+//! ```text
+//! arguments [=> expression] [; "comment"] [; option]*
+//! ```
+//!
+//! Each part ends at the next top-level `=>` or `;`. A malformed part does not stop parsing: its
+//! remaining tokens are skipped and errors of all parts are reported together.
+
+use crate::comment::TestCaseComment;
+use crate::expr::TestCaseExpression;
+use crate::options::TestCaseOptions;
+use crate::utils::Errors;
+use proc_macro2::TokenTree;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, LitStr, Token};
+
+pub struct Declaration {
+    pub args: Punctuated<Expr, Token![,]>,
+    pub expression: Option<TestCaseExpression>,
+    pub comment: Option<TestCaseComment>,
+    pub options: TestCaseOptions,
+}
+
+impl Parse for Declaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut errors = Errors::default();
+
+        let args = part(
+            &mut errors,
+            input,
+            end_of_arguments,
+            "`,`, `=>` or `;`",
+            |input| Punctuated::parse_separated_nonempty_with(input, Expr::parse),
+        );
+        let expression = if input.peek(Token![=>]) {
+            part(
+                &mut errors,
+                input,
+                end_of_part,
+                "`;`",
+                TestCaseExpression::parse,
+            )
+        } else {
+            None
+        };
+        let comment = if input.peek(Token![;]) && input.peek2(LitStr) {
+            part(
+                &mut errors,
+                input,
+                end_of_part,
+                "`;`",
+                TestCaseComment::parse,
+            )
+        } else {
+            None
+        };
+        let mut options = TestCaseOptions::default();
+        while input.peek(Token![;]) {
+            part(&mut errors, input, end_of_part, "`;`", |input| {
+                let _: Token![;] = input.parse()?;
+                options.parse_option(input)
+            });
+        }
+
+        errors.finish()?;
+
+        Ok(Self {
+            args: args.expect("arguments are parsed unless there is an error"),
+            expression,
+            comment,
+            options,
+        })
+    }
+}
+
+fn end_of_arguments(input: ParseStream) -> bool {
+    end_of_part(input) || input.peek(Token![=>])
+}
+
+fn end_of_part(input: ParseStream) -> bool {
+    input.is_empty() || input.peek(Token![;])
+}
+
+/// Parses a part which has to be followed by a token accepted by `end`. On failure the error is
+/// recorded and tokens are skipped up to the end of the part.
+fn part<T>(
+    errors: &mut Errors,
+    input: ParseStream,
+    end: fn(ParseStream) -> bool,
+    expected: &str,
+    parse: impl FnOnce(ParseStream) -> syn::Result<T>,
+) -> Option<T> {
+    let result = parse(input).and_then(|value| {
+        if end(input) {
+            Ok(value)
+        } else {
+            Err(input.error(format!("unexpected token, expected {expected}")))
+        }
+    });
+
+    match result {
+        Ok(value) => Some(value),
+        Err(error) => {
+            errors.push(error);
+            while !end(input) {
+                let _ = input.parse::<TokenTree>();
+            }
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::declaration::Declaration;
+    use crate::expr::TestCaseResult;
+
+    fn errors(input: &str) -> Vec<String> {
+        match syn::parse_str::<Declaration>(input) {
+            Ok(_) => vec![],
+            Err(error) => error.into_iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_all_parts() {
+        let actual: Declaration =
+            syn::parse_str(r#"1, 2 => 3 ; "comment" ; types(u8) ; test_attr = my_test"#).unwrap();
+        assert_eq!(actual.args.len(), 2);
+        assert!(actual.expression.is_some());
+        assert_eq!(actual.comment.unwrap().comment.value(), "comment");
+        assert_eq!(actual.options.types.len(), 1);
+        assert!(actual.options.test_attr.is_some());
+    }
+
+    #[test]
+    fn keeps_expression_followed_by_comment() {
+        let actual: Declaration = syn::parse_str(r#"1 => panics ; "comment""#).unwrap();
+        assert!(matches!(
+            actual.expression.unwrap().result,
+            TestCaseResult::Panicking(None)
+        ));
+        assert!(actual.comment.is_some());
+
+        let actual: Declaration = syn::parse_str(r#"1 => ignore ; "comment""#).unwrap();
+        assert!(matches!(
+            actual.expression.unwrap().result,
+            TestCaseResult::Empty
+        ));
+        assert!(actual.comment.is_some());
+    }
+
+    #[test]
+    fn reports_malformed_parts() {
+        let cases: &[(&str, &[&str])] = &[
+            ("", &["unexpected end of input, expected an expression"]),
+            ("=> 1", &["expected an expression"]),
+            ("1, => 2", &["expected an expression"]),
+            ("1 2 => 3", &["unexpected token, expected `,`, `=>` or `;`"]),
+            (r#"1 => 2 3 ; "comment""#, &["unexpected token, expected `;`"]),
+            (r#"1 => matches ; "comment""#, &["expected pattern after `matches`"]),
+            (
+                r#"1 => is ; "comment""#,
+                &["cannot parse complex expression"],
+            ),
+            (
+                r#"1 ; "comment" ; "another comment""#,
+                &["unknown test case option, expected `runtime = ...`, `test_attr = ...`, `receiver = ...`, `types(...)` or `consts(...)`"],
+            ),
+            (
+                "1 ; test_attr = my_test extra",
+                &["unexpected token, expected `;`"],
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(&errors(input), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn reports_all_malformed_parts_at_once() {
+        assert_eq!(
+            errors(r#"1 2 => is lenght 3 ; "comment" ; runtime = tokyo ; types u8"#),
+            [
+                "unexpected token, expected `,`, `=>` or `;`",
+                "unknown keyword `lenght`, did you mean `len`?",
+                "unknown runtime, expected `tokio`, `async_std` or `smol`",
+                "expected parentheses",
+            ]
+        );
+    }
+}
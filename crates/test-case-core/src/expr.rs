@@ -1,21 +1,29 @@
 use crate::complex_expr::ComplexTestCase;
-use crate::modifier::{parse_kws, Modifier};
-use crate::utils::fmt_syn;
+use crate::modifier::{self, parse_kws, Modifier};
+use crate::suggestions::misspelled_keyword;
+use crate::utils::{fmt_syn, span_of};
 use crate::TokenStream2;
-use quote::ToTokens;
+use quote::{quote_spanned, ToTokens};
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use syn::parse::{Parse, ParseStream};
 use syn::token::If;
-use syn::{parse_quote, Attribute, Expr, Pat, Token};
+use syn::{parse_quote, Attribute, Expr, ExprClosure, Ident, LitStr, Pat, ReturnType, Token};
 
 pub mod kw {
-    syn::custom_keyword!(matches);
-    syn::custom_keyword!(using);
-    syn::custom_keyword!(with);
-    syn::custom_keyword!(it);
-    syn::custom_keyword!(is);
-    syn::custom_keyword!(panics);
+    crate::suggestions::keywords!(
+        matches,
+        using,
+        with,
+        it,
+        is,
+        panics,
+        equals_file,
+        snapshot,
+        debug,
+        json,
+        yaml,
+    );
 }
 
 #[derive(Clone, Debug)]
@@ -39,16 +47,36 @@ pub enum TestCaseResult {
     With(Expr),
     // test_case(a, b, c => using assert_nan)
     UseFn(Expr),
+    // test_case(a, b, c => with async |v: T| assert!(query(v).await))
+    WithAsync(Expr),
+    // test_case(a, b, c => using async assert_in_db)
+    UseAsyncFn(Expr),
     // test_case(a, b, c => is close to 4 precision 0.1)
     Complex(ComplexTestCase),
+    // test_case(a, b, c => equals_file "golden/{case}.txt")
+    EqualsFile(LitStr),
+    // test_case(a, b, c => snapshot json)
+    #[cfg(feature = "insta")]
+    Snapshot(SnapshotFormat),
+}
+
+#[cfg(feature = "insta")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    Debug,
+    Json,
+    Yaml,
 }
 
 impl Parse for TestCaseExpression {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let token: Token![=>] = input.parse()?;
-        let extra_keywords = parse_kws(input);
+        let extra_keywords = parse_kws(input)?;
 
         if input.parse::<kw::matches>().is_ok() {
+            if input.is_empty() || input.peek(Token![;]) {
+                return Err(input.error("expected pattern after `matches`"));
+            }
             let pattern = Pat::parse_single(input)?;
             let guard = if input.peek(If) {
                 let _if_kw: If = input.parse()?;
@@ -66,17 +94,52 @@ impl Parse for TestCaseExpression {
         } else if input.parse::<kw::it>().is_ok() || input.parse::<kw::is>().is_ok() {
             parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::Complex)
         } else if input.parse::<kw::using>().is_ok() {
-            parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::UseFn)
+            if input.parse::<Token![async]>().is_ok() {
+                parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::UseAsyncFn)
+            } else {
+                parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::UseFn)
+            }
         } else if input.parse::<kw::with>().is_ok() {
-            parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::With)
+            if input.parse::<Token![async]>().is_ok() {
+                parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::WithAsync)
+            } else {
+                parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::With)
+            }
         } else if input.parse::<kw::panics>().is_ok() {
             parse_with_keyword_ok::<_, _>(input, token, extra_keywords, TestCaseResult::Panicking)
+        } else if input.parse::<kw::equals_file>().is_ok() {
+            parse_with_keyword::<_, _>(input, token, extra_keywords, TestCaseResult::EqualsFile)
+        } else if peek_snapshot(input) {
+            let _: kw::snapshot = input.parse()?;
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "insta")] {
+                    let format = if input.parse::<kw::json>().is_ok() {
+                        SnapshotFormat::Json
+                    } else if input.parse::<kw::yaml>().is_ok() {
+                        SnapshotFormat::Yaml
+                    } else {
+                        let _ = input.parse::<kw::debug>();
+                        SnapshotFormat::Debug
+                    };
+
+                    Ok(TestCaseExpression {
+                        _token: token,
+                        extra_keywords,
+                        result: TestCaseResult::Snapshot(format),
+                    })
+                } else {
+                    Err(input.error("'insta' feature is required to use 'snapshot' keyword"))
+                }
+            }
+        } else if let Some(error) = misspelled_result_keyword(input) {
+            Err(error)
         } else {
-            let result = match input.parse::<Expr>() {
-                Ok(expr) => TestCaseResult::Simple(expr),
-                Err(_) if !extra_keywords.is_empty() => TestCaseResult::Empty,
-                Err(e) => return Err(e),
-            };
+            let result =
+                if !extra_keywords.is_empty() && (input.is_empty() || input.peek(Token![;])) {
+                    TestCaseResult::Empty
+                } else {
+                    TestCaseResult::Simple(input.parse()?)
+                };
 
             Ok(Self {
                 _token: token,
@@ -110,30 +173,52 @@ impl Display for TestCaseResult {
             ),
             TestCaseResult::With(expr) => write!(f, "with {}", fmt_syn(expr)),
             TestCaseResult::UseFn(expr) => write!(f, "use {}", fmt_syn(expr)),
+            TestCaseResult::WithAsync(expr) => write!(f, "with async {}", fmt_syn(expr)),
+            TestCaseResult::UseAsyncFn(expr) => write!(f, "use async {}", fmt_syn(expr)),
             TestCaseResult::Complex(complex) => write!(f, "complex {complex}"),
             TestCaseResult::Empty => write!(f, "empty"),
+            TestCaseResult::EqualsFile(path) => write!(f, "equals file {}", path.value()),
+            #[cfg(feature = "insta")]
+            TestCaseResult::Snapshot(format) => write!(f, "snapshot {format}"),
+        }
+    }
+}
+
+#[cfg(feature = "insta")]
+impl Display for SnapshotFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotFormat::Debug => f.write_str("debug"),
+            SnapshotFormat::Json => f.write_str("json"),
+            SnapshotFormat::Yaml => f.write_str("yaml"),
         }
     }
 }
 
 impl TestCaseExpression {
-    pub fn assertion(&self) -> TokenStream2 {
+    /// `case_name` replaces `{case}` placeholder of `equals_file` path.
+    pub fn assertion(
+        &self,
+        test_case_name: &Ident,
+        case_name: &str,
+        source: &TokenStream2,
+    ) -> TokenStream2 {
         match &self.result {
-            TestCaseResult::Simple(expr) => parse_quote! { assert_eq!(_result, #expr) },
+            TestCaseResult::Simple(expr) => simple_assertion(expr, test_case_name, source),
             TestCaseResult::Matching(pat, guard) => {
                 let pat_str = pat.to_token_stream().to_string();
 
                 if let Some(guard) = guard {
                     let guard_str = guard.to_token_stream().to_string();
 
-                    parse_quote! {
+                    quote_spanned! {span_of(pat)=>
                         match _result {
                             #pat if #guard => (),
                             e => panic!("Expected `{} if {}` found {:?}", #pat_str, #guard_str, e)
                         }
                     }
                 } else {
-                    parse_quote! {
+                    quote_spanned! {span_of(pat)=>
                         match _result {
                             #pat => (),
                             e => panic!("Expected `{}` found {:?}", #pat_str, e)
@@ -142,13 +227,36 @@ impl TestCaseExpression {
                 }
             }
             TestCaseResult::Panicking(_) => TokenStream2::new(),
-            TestCaseResult::With(expr) => parse_quote! { let fun = #expr; fun(_result) },
-            TestCaseResult::UseFn(path) => parse_quote! { #path(_result) },
+            TestCaseResult::With(expr) => {
+                quote_spanned! {span_of(expr)=> let fun = #expr; fun(_result) }
+            }
+            TestCaseResult::UseFn(path) => quote_spanned! {span_of(path)=> #path(_result) },
+            TestCaseResult::WithAsync(expr) => {
+                let span = span_of(expr);
+                let expr = async_closure(expr);
+                quote_spanned! {span=> let fun = #expr; fun(_result).await }
+            }
+            TestCaseResult::UseAsyncFn(path) => {
+                quote_spanned! {span_of(path)=> #path(_result).await }
+            }
             TestCaseResult::Complex(complex) => complex.assertion(),
             TestCaseResult::Empty => TokenStream2::new(),
+            TestCaseResult::EqualsFile(path) => {
+                equals_file_assertion(path, test_case_name, case_name)
+            }
+            #[cfg(feature = "insta")]
+            TestCaseResult::Snapshot(format) => snapshot_assertion(*format, test_case_name),
         }
     }
 
+    /// Whether assertion has to be awaited, hence requires async test function.
+    pub fn is_async(&self) -> bool {
+        matches!(
+            self.result,
+            TestCaseResult::WithAsync(_) | TestCaseResult::UseAsyncFn(_)
+        )
+    }
+
     pub fn attributes(&self) -> Vec<Attribute> {
         let mut attrs: Vec<Attribute> = self
             .extra_keywords
@@ -166,6 +274,90 @@ impl TestCaseExpression {
     }
 }
 
+/// Turns `|v| body` into `|v| async move { body }`, so that the body can `.await`. Other
+/// expressions are expected to evaluate to a function returning a future already.
+fn async_closure(expr: &Expr) -> Expr {
+    match expr {
+        Expr::Closure(closure) if closure.asyncness.is_none() => {
+            let body = &closure.body;
+            let closure = ExprClosure {
+                output: ReturnType::Default,
+                body: Box::new(parse_quote! { async move { #body } }),
+                ..closure.clone()
+            };
+            Expr::Closure(closure)
+        }
+        expr => expr.clone(),
+    }
+}
+
+fn simple_assertion(expr: &Expr, test_case_name: &Ident, source: &TokenStream2) -> TokenStream2 {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "pretty-diff")] {
+            let test_case_name = test_case_name.to_string();
+            let source = source.to_string();
+            quote_spanned! {span_of(expr)=>
+                ::test_case::internal::diff::assert_eq(&_result, &#expr, #test_case_name, #source)
+            }
+        } else {
+            let _ = (test_case_name, source);
+            quote_spanned! {span_of(expr)=> assert_eq!(_result, #expr) }
+        }
+    }
+}
+
+/// `{case}` placeholder in the path is replaced with `case_name`.
+fn equals_file_assertion(path: &LitStr, test_case_name: &Ident, case_name: &str) -> TokenStream2 {
+    let test_case_name = test_case_name.to_string();
+    let path = path.value().replace("{case}", case_name);
+
+    parse_quote! {
+        {
+            #[allow(unused_imports)]
+            use ::test_case::internal::golden::{DisplayContent as _, StrContent as _};
+
+            let _tc_content = (&::test_case::internal::golden::Content(&_result)).content();
+            ::test_case::internal::golden::assert_file_eq(
+                &_tc_content,
+                ::core::env!("CARGO_MANIFEST_DIR"),
+                #path,
+                #test_case_name,
+            )
+        }
+    }
+}
+
+/// `snapshot` is a keyword when followed by snapshot format or, with 'insta' feature, when it ends
+/// the expression. Otherwise it is an ordinary expression, e.g. a variable named `snapshot`.
+fn peek_snapshot(input: ParseStream) -> bool {
+    let fork = input.fork();
+    if fork.parse::<kw::snapshot>().is_err() {
+        return false;
+    }
+
+    fork.peek(kw::debug)
+        || fork.peek(kw::json)
+        || fork.peek(kw::yaml)
+        || (cfg!(feature = "insta") && (fork.is_empty() || fork.peek(Token![;])))
+}
+
+#[cfg(feature = "insta")]
+fn snapshot_assertion(format: SnapshotFormat, test_case_name: &Ident) -> TokenStream2 {
+    let snapshot_name = test_case_name.to_string();
+
+    match format {
+        SnapshotFormat::Debug => parse_quote! {
+            ::test_case::internal::insta::assert_debug_snapshot!(#snapshot_name, _result)
+        },
+        SnapshotFormat::Json => parse_quote! {
+            ::test_case::internal::insta::assert_json_snapshot!(#snapshot_name, _result)
+        },
+        SnapshotFormat::Yaml => parse_quote! {
+            ::test_case::internal::insta::assert_yaml_snapshot!(#snapshot_name, _result)
+        },
+    }
+}
+
 fn parse_with_keyword<Inner, Mapping>(
     input: ParseStream,
     token: Token![=>],
@@ -193,10 +385,67 @@ where
     Mapping: FnOnce(Option<Inner>) -> TestCaseResult,
     Inner: Parse,
 {
-    let result = (!input.is_empty()).then(|| input.parse()).transpose()?;
+    let result = (!input.is_empty() && !input.peek(Token![;]))
+        .then(|| input.parse())
+        .transpose()?;
     Ok(TestCaseExpression {
         _token: token,
         extra_keywords,
         result: mapping(result),
     })
 }
+
+/// Bare identifier followed by further tokens, e.g. `=> panic "reason"`, is not a valid expected
+/// value and most likely is a misspelled keyword.
+fn misspelled_result_keyword(input: ParseStream) -> Option<syn::Error> {
+    let fork = input.fork();
+    let is_bare_ident = matches!(
+        fork.parse::<Expr>().ok()?,
+        Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some()
+    );
+    if !is_bare_ident || fork.is_empty() || fork.peek(Token![;]) {
+        return None;
+    }
+
+    misspelled_keyword(input, kw::ALL.iter().chain(modifier::kw::ALL).copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expr::{TestCaseExpression, TestCaseResult};
+
+    fn parse(input: &str) -> syn::Result<TestCaseResult> {
+        syn::parse_str::<TestCaseExpression>(input).map(|expression| expression.result)
+    }
+
+    #[test]
+    fn parses_snapshot_followed_by_other_tokens_as_expression() {
+        assert!(matches!(
+            parse("=> snapshot.len()").unwrap(),
+            TestCaseResult::Simple(_)
+        ));
+        assert!(matches!(
+            parse("=> snapshot + 1").unwrap(),
+            TestCaseResult::Simple(_)
+        ));
+    }
+
+    #[test]
+    fn parses_bare_snapshot_as_keyword_only_with_insta() {
+        let result = parse("=> snapshot").unwrap();
+        if cfg!(feature = "insta") {
+            assert!(!matches!(result, TestCaseResult::Simple(_)));
+        } else {
+            assert!(matches!(result, TestCaseResult::Simple(_)));
+        }
+    }
+
+    #[cfg(not(feature = "insta"))]
+    #[test]
+    fn requires_insta_for_snapshot_with_format() {
+        assert_eq!(
+            parse("=> snapshot json").unwrap_err().to_string(),
+            "'insta' feature is required to use 'snapshot' keyword"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use syn::{parse_quote, Attribute, FnArg, ItemFn, Path};
+
+pub(crate) fn is_fixture_attribute(attr: &Attribute) -> bool {
+    let legal_fixture_names: [Path; 2] = [parse_quote!(fixture), parse_quote!(test_case::fixture)];
+    legal_fixture_names.contains(attr.path())
+}
+
+/// Removes `#[fixture]` attributes from parameters of the function, they are meaningful only to
+/// `test_case` macros.
+pub fn remove_fixture_attributes(item: &mut ItemFn) {
+    for input in item.sig.inputs.iter_mut() {
+        if let FnArg::Typed(arg) = input {
+            arg.attrs.retain(|attr| !is_fixture_attribute(attr));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::TokenStream2;
+use quote::quote;
+use syn::{Error, Expr, FnArg, ItemFn, Type};
+
+/// Functions called around every test case of a function, declared with
+/// `#[test_case_setup(make_fixture)]` and `#[test_case_teardown(cleanup)]`.
+///
+/// Value returned by setup is passed as the last argument of test function. When teardown is
+/// declared as well, test function receives a reference to the value (`&T` or `&mut T`, depending on
+/// the type of its last parameter) and teardown receives the value itself once test case ends.
+/// Otherwise teardown is called without arguments. Teardown runs even if test case panics. It runs
+/// from a guard's `Drop`, so teardown panicking while a failed case unwinds aborts the process.
+#[derive(Clone, Debug, Default)]
+pub struct Hooks {
+    pub setup: Option<Expr>,
+    pub teardown: Option<Expr>,
+}
+
+impl Hooks {
+    /// Statements to run before calling test function and the extra argument to pass to it.
+    pub(crate) fn prepare(
+        &self,
+        item: &ItemFn,
+    ) -> Result<(TokenStream2, Option<TokenStream2>), Error> {
+        Ok(match (&self.setup, &self.teardown) {
+            (None, None) => (TokenStream2::new(), None),
+            (Some(setup), None) => (
+                quote! { let _tc_fixture = (#setup)(); },
+                Some(quote! { _tc_fixture }),
+            ),
+            (None, Some(teardown)) => (
+                quote! { let _tc_teardown = ::test_case::internal::hooks::Teardown::new(#teardown); },
+                None,
+            ),
+            (Some(setup), Some(teardown)) => {
+                let argument = match item.sig.inputs.last() {
+                    Some(FnArg::Typed(arg)) => match arg.ty.as_ref() {
+                        Type::Reference(reference) if reference.mutability.is_some() => {
+                            quote! { &mut *_tc_fixture }
+                        }
+                        Type::Reference(_) => quote! { &*_tc_fixture },
+                        ty => {
+                            return Err(Error::new_spanned(
+                                ty,
+                                "value returned by test_case_setup is passed by reference when test_case_teardown is declared, expected `&T` or `&mut T`",
+                            ))
+                        }
+                    },
+                    _ => {
+                        return Err(Error::new_spanned(
+                            &item.sig,
+                            "test function has to accept value returned by test_case_setup as the last parameter",
+                        ))
+                    }
+                };
+
+                (
+                    quote! {
+                        let mut _tc_fixture =
+                            ::test_case::internal::hooks::Fixture::new((#setup)(), #teardown);
+                    },
+                    Some(argument),
+                )
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hooks::Hooks;
+    use syn::{parse_quote, ItemFn};
+
+    fn both() -> Hooks {
+        Hooks {
+            setup: Some(parse_quote! { make_fixture }),
+            teardown: Some(parse_quote! { cleanup }),
+        }
+    }
+
+    #[test]
+    fn passes_fixture_by_reference_when_teardown_is_declared() {
+        let item: ItemFn = parse_quote! { fn f(x: u32, fixture: &mut Fixture) {} };
+        let (_, argument) = both().prepare(&item).unwrap();
+        assert_eq!(
+            argument.unwrap().to_string(),
+            quote::quote! { &mut *_tc_fixture }.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_fixture_by_value_when_teardown_is_declared() {
+        let item: ItemFn = parse_quote! { fn f(x: u32, fixture: Fixture) {} };
+        let error = both().prepare(&item).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "value returned by test_case_setup is passed by reference when test_case_teardown is declared, expected `&T` or `&mut T`"
+        );
+    }
+}
@@ -1,12 +1,29 @@
 use proc_macro2::TokenStream as TokenStream2;
 
+mod arguments;
+mod case_set;
 mod comment;
 mod complex_expr;
+mod declaration;
 mod expr;
+mod fixture;
+mod hooks;
 mod modifier;
+mod options;
+mod suggestions;
+mod suite;
 mod test_case;
 mod test_matrix;
 mod utils;
 
+pub use arguments::TestCaseDefaults;
+pub use case_set::CaseSets;
+pub use fixture::remove_fixture_attributes;
+pub use hooks::Hooks;
+pub use suite::{ApplySuite, TestCaseSuite};
 pub use test_case::TestCase;
 pub use test_matrix::TestMatrix;
+#[doc(hidden)]
+pub use utils::to_snake_case;
+#[doc(hidden)]
+pub use utils::Errors;
@@ -1,12 +1,12 @@
+use crate::suggestions::misspelled_keyword;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 use syn::parse::{Parse, ParseStream};
 use syn::token::Bracket;
 use syn::{bracketed, parse_quote, Attribute, LitStr};
 
-mod kw {
-    syn::custom_keyword!(inconclusive);
-    syn::custom_keyword!(ignore);
+pub mod kw {
+    crate::suggestions::keywords!(inconclusive, ignore,);
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -34,7 +34,8 @@ impl Parse for Modifier {
             let _: kw::ignore = input.parse()?;
             Self::parse_inconclusive(input)
         } else {
-            Err(syn::Error::new(input.span(), "unknown modifier keyword"))
+            Err(misspelled_keyword(input, kw::ALL.iter().copied())
+                .unwrap_or_else(|| syn::Error::new(input.span(), "unknown modifier keyword")))
         }
     }
 }
@@ -59,10 +60,10 @@ impl Modifier {
     }
 }
 
-pub fn parse_kws(input: ParseStream) -> HashSet<Modifier> {
+pub fn parse_kws(input: ParseStream) -> syn::Result<HashSet<Modifier>> {
     let mut kws = HashSet::new();
-    while let Ok(kw) = Modifier::parse(input) {
-        kws.insert(kw);
+    while input.peek(kw::inconclusive) || input.peek(kw::ignore) {
+        kws.insert(Modifier::parse(input)?);
     }
-    kws
+    Ok(kws)
 }
@@ -0,0 +1,342 @@
+use crate::utils::fmt_syn;
+use crate::TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    bracketed, parenthesized, Error, Expr, GenericParam, Generics, Ident, Meta, Token, Type,
+};
+
+mod kw {
+    syn::custom_keyword!(runtime);
+    syn::custom_keyword!(test_attr);
+    syn::custom_keyword!(receiver);
+    syn::custom_keyword!(types);
+    syn::custom_keyword!(consts);
+    syn::custom_keyword!(tokio);
+    syn::custom_keyword!(async_std);
+    syn::custom_keyword!(smol);
+}
+
+/// Options following test case description, e.g. `#[test_case(1 => 2 ; "one" ; runtime = tokio)]`.
+#[derive(Clone, Debug, Default)]
+pub struct TestCaseOptions {
+    pub runtime: Option<Runtime>,
+    /// Attribute used instead of `#[test]`, e.g. `test_attr = wasm_bindgen_test`.
+    pub test_attr: Option<Meta>,
+    /// Instance the method is called on, e.g. `receiver = Foo::new()`.
+    pub receiver: Option<Expr>,
+    /// Types the generic test function is instantiated with, e.g. `types(u8, u16)`.
+    pub types: Vec<Type>,
+    /// Values of const generic parameters, e.g. `consts(N = [1, 4, 16])`.
+    pub consts: Vec<ConstValues>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConstValues {
+    pub param: Ident,
+    pub values: Vec<Expr>,
+}
+
+/// Generic arguments of a single instantiation of the test function along with test name suffix.
+#[derive(Clone, Debug, Default)]
+pub struct GenericInstance {
+    pub suffix: String,
+    pub args: Vec<TokenStream2>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Runtime {
+    Tokio,
+    AsyncStd,
+    Smol,
+}
+
+impl Parse for ConstValues {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let param = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let content;
+        bracketed!(content in input);
+        let values = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+
+        Ok(Self {
+            param,
+            values: values.into_iter().collect(),
+        })
+    }
+}
+
+impl TestCaseOptions {
+    /// Parses a single option, not preceded by `;`, e.g. `runtime = tokio`.
+    pub fn parse_option(&mut self, input: ParseStream) -> syn::Result<()> {
+        if input.parse::<kw::runtime>().is_ok() {
+            let _: Token![=] = input.parse()?;
+            self.runtime = Some(input.parse()?);
+        } else if input.parse::<kw::test_attr>().is_ok() {
+            let _: Token![=] = input.parse()?;
+            self.test_attr = Some(input.parse()?);
+        } else if input.parse::<kw::receiver>().is_ok() {
+            let _: Token![=] = input.parse()?;
+            self.receiver = Some(input.parse()?);
+        } else if input.parse::<kw::types>().is_ok() {
+            let content;
+            parenthesized!(content in input);
+            let types = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+            self.types.extend(types);
+        } else if input.parse::<kw::consts>().is_ok() {
+            let content;
+            parenthesized!(content in input);
+            let consts = Punctuated::<ConstValues, Token![,]>::parse_terminated(&content)?;
+            self.consts.extend(consts);
+        } else {
+            return Err(input.error(
+                "unknown test case option, expected `runtime = ...`, `test_attr = ...`, `receiver = ...`, `types(...)` or `consts(...)`",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Instantiations of generic test function requested with `types(...)` and `consts(...)`, one
+    /// per combination of given types and const values. Empty if neither option is used.
+    ///
+    /// Type parameters are given `types` (or inferred with `_` when only `consts` are listed), every
+    /// const parameter needs its values listed in `consts`.
+    pub fn generic_instances(&self, generics: &Generics) -> syn::Result<Vec<GenericInstance>> {
+        if self.types.is_empty() && self.consts.is_empty() {
+            return Ok(vec![]);
+        }
+        if !self.types.is_empty() && generics.type_params().count() != 1 {
+            return Err(Error::new_spanned(
+                generics,
+                "`types(...)` option requires test function with exactly one type parameter",
+            ));
+        }
+        if let Some(unknown) = self
+            .consts
+            .iter()
+            .find(|c| generics.const_params().all(|param| param.ident != c.param))
+        {
+            return Err(Error::new_spanned(
+                &unknown.param,
+                format!("test function has no const parameter `{}`", unknown.param),
+            ));
+        }
+
+        let mut instances = vec![GenericInstance::default()];
+        for param in &generics.params {
+            let choices: Vec<(TokenStream2, Option<String>)> = match param {
+                GenericParam::Lifetime(_) => continue,
+                GenericParam::Type(_) if self.types.is_empty() => vec![(quote! { _ }, None)],
+                GenericParam::Type(_) => self
+                    .types
+                    .iter()
+                    .map(|ty| (quote! { #ty }, Some(fmt_syn(ty))))
+                    .collect(),
+                GenericParam::Const(param) => {
+                    let consts = self
+                        .consts
+                        .iter()
+                        .find(|c| c.param == param.ident)
+                        .ok_or_else(|| {
+                            Error::new_spanned(
+                                &param.ident,
+                                format!(
+                                    "missing values of const parameter `{}`, list them in `consts(...)` option",
+                                    param.ident
+                                ),
+                            )
+                        })?;
+                    consts
+                        .values
+                        .iter()
+                        .map(|value| {
+                            let suffix = format!("{}_{}", consts.param, fmt_syn(value));
+                            (quote! { #value }, Some(suffix))
+                        })
+                        .collect()
+                }
+            };
+
+            instances = instances
+                .into_iter()
+                .flat_map(|instance| {
+                    choices.iter().map(move |(arg, suffix)| {
+                        let mut instance = instance.clone();
+                        instance.args.push(arg.clone());
+                        if let Some(suffix) = suffix {
+                            instance.suffix.push('_');
+                            instance.suffix.push_str(suffix);
+                        }
+                        instance
+                    })
+                })
+                .collect();
+        }
+
+        Ok(instances)
+    }
+}
+
+impl Parse for Runtime {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let (runtime, feature) = if input.parse::<kw::tokio>().is_ok() {
+            (Runtime::Tokio, "tokio")
+        } else if input.parse::<kw::async_std>().is_ok() {
+            (Runtime::AsyncStd, "async-std")
+        } else if input.parse::<kw::smol>().is_ok() {
+            (Runtime::Smol, "smol")
+        } else {
+            return Err(input.error("unknown runtime, expected `tokio`, `async_std` or `smol`"));
+        };
+
+        if runtime.is_enabled() {
+            Ok(runtime)
+        } else {
+            Err(syn::Error::new(
+                span,
+                format!("'{feature}' feature is required to use 'runtime = {runtime}' option"),
+            ))
+        }
+    }
+}
+
+impl std::fmt::Display for Runtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Runtime::Tokio => f.write_str("tokio"),
+            Runtime::AsyncStd => f.write_str("async_std"),
+            Runtime::Smol => f.write_str("smol"),
+        }
+    }
+}
+
+impl Runtime {
+    fn is_enabled(self) -> bool {
+        match self {
+            Runtime::Tokio => cfg!(feature = "tokio"),
+            Runtime::AsyncStd => cfg!(feature = "async-std"),
+            Runtime::Smol => cfg!(feature = "smol"),
+        }
+    }
+
+    /// Runs `body` to completion on this runtime from within synchronous test function.
+    pub fn block_on(self, body: TokenStream2) -> TokenStream2 {
+        let runtime = match self {
+            Runtime::Tokio => quote! { tokio },
+            Runtime::AsyncStd => quote! { async_std },
+            Runtime::Smol => quote! { smol },
+        };
+
+        quote! { ::test_case::internal::runtime::#runtime(async move { #body }) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::declaration::Declaration;
+    use crate::options::TestCaseOptions;
+    use syn::{parse_quote, Generics};
+
+    /// Options of test case declaration with a single argument, e.g. `options("; types(u8)")`.
+    fn options(input: &str) -> syn::Result<TestCaseOptions> {
+        syn::parse_str::<Declaration>(&format!("1 {input}")).map(|declaration| declaration.options)
+    }
+
+    #[test]
+    fn parses_empty_options() {
+        let actual = options("").unwrap();
+        assert_eq!(actual.runtime, None);
+        assert_eq!(actual.test_attr, None);
+        assert_eq!(actual.receiver, None);
+    }
+
+    #[test]
+    fn parses_receiver() {
+        let actual = options("; receiver = Foo::new(1) ; test_attr = tokio::test").unwrap();
+        assert_eq!(actual.receiver, Some(parse_quote! { Foo::new(1) }));
+    }
+
+    #[test]
+    fn parses_test_attr() {
+        let actual = options("; test_attr = wasm_bindgen_test::wasm_bindgen_test").unwrap();
+        assert_eq!(
+            actual.test_attr,
+            Some(parse_quote! { wasm_bindgen_test::wasm_bindgen_test })
+        );
+        let actual = options("; test_attr = my_harness::test(timeout = 10)").unwrap();
+        assert_eq!(
+            actual.test_attr,
+            Some(parse_quote! { my_harness::test(timeout = 10) })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_option() {
+        let error = options("; executor = tokio").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown test case option, expected `runtime = ...`, `test_attr = ...`, `receiver = ...`, `types(...)` or `consts(...)`"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_runtime() {
+        let error = options("; runtime = glommio").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown runtime, expected `tokio`, `async_std` or `smol`"
+        );
+    }
+
+    #[test]
+    fn instantiates_every_combination_of_types_and_consts() {
+        let options = options("; types(u8, Vec<u16>) ; consts(N = [1, 2])").unwrap();
+        let generics: Generics = parse_quote! { <'a, T: Copy, const N: usize> };
+        let instances = options.generic_instances(&generics).unwrap();
+        let actual: Vec<(String, String)> = instances
+            .iter()
+            .map(|instance| {
+                let args = &instance.args;
+                (
+                    instance.suffix.clone(),
+                    quote::quote! { #(#args),* }.to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            actual,
+            [
+                ("_u8_N_1", "u8 , 1"),
+                ("_u8_N_2", "u8 , 2"),
+                ("_Vec < u16 >_N_1", "Vec < u16 > , 1"),
+                ("_Vec < u16 >_N_2", "Vec < u16 > , 2"),
+            ]
+            .map(|(suffix, args)| (suffix.to_string(), args.to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_missing_const_values() {
+        let options = options("; types(u8)").unwrap();
+        let generics: Generics = parse_quote! { <T, const N: usize> };
+        let error = options.generic_instances(&generics).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "missing values of const parameter `N`, list them in `consts(...)` option"
+        );
+    }
+
+    #[test]
+    fn rejects_types_for_multiple_type_parameters() {
+        let options = options("; types(u8)").unwrap();
+        let generics: Generics = parse_quote! { <T, U> };
+        let error = options.generic_instances(&generics).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`types(...)` option requires test function with exactly one type parameter"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! "Did you mean" suggestions for misspelled keywords.
+
+use proc_macro2::Ident;
+use syn::parse::ParseStream;
+
+/// Declares custom keywords together with `ALL` list of their names, used to suggest the closest
+/// keyword when parsing fails.
+macro_rules! keywords {
+    ($($kw:ident),* $(,)?) => {
+        $(syn::custom_keyword!($kw);)*
+
+        pub const ALL: &[&str] = &[$(stringify!($kw)),*];
+    };
+}
+
+pub(crate) use keywords;
+
+/// Keywords which are parsed only when given feature is enabled.
+const FEATURE_GATED: &[(&str, &str, bool)] = &[
+    ("matching_regex", "with-regex", cfg!(feature = "with-regex")),
+    ("matches_regex", "with-regex", cfg!(feature = "with-regex")),
+    ("json_eq", "with-json", cfg!(feature = "with-json")),
+    ("json_subset", "with-json", cfg!(feature = "with-json")),
+    ("snapshot", "insta", cfg!(feature = "insta")),
+];
+
+/// Error for identifier at the front of `input` which is not a keyword but is close enough to one
+/// of `candidates` to be a typo. Returns `None` if there is no such identifier.
+pub fn misspelled_keyword<'a>(
+    input: ParseStream,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<syn::Error> {
+    let ident: Ident = input.fork().parse().ok()?;
+    let word = ident.to_string();
+    let suggestion = closest(&word, candidates)?;
+
+    let mut message = format!("unknown keyword `{word}`, did you mean `{suggestion}`?");
+    if let Some((_, feature, _)) = FEATURE_GATED
+        .iter()
+        .find(|(keyword, _, enabled)| *keyword == suggestion && !enabled)
+    {
+        message.push_str(&format!(
+            " note: `{suggestion}` requires '{feature}' feature"
+        ));
+    }
+
+    Some(syn::Error::new(ident.span(), message))
+}
+
+/// Candidate with the smallest edit distance to `word`, if it is close enough to be a typo.
+fn closest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = word.chars().count().max(3) / 2;
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|(distance, _)| (1..=max_distance).contains(distance))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse::Parser;
+
+    fn suggest(input: &str, candidates: &'static [&'static str]) -> Option<String> {
+        let parser = |input: ParseStream| {
+            let error = misspelled_keyword(input, candidates.iter().copied());
+            input.parse::<proc_macro2::TokenStream>()?;
+            Ok(error.map(|e| e.to_string()))
+        };
+        parser.parse_str(input).unwrap()
+    }
+
+    #[test]
+    fn edit_distance_counts_insertions_deletions_and_substitutions() {
+        assert_eq!(edit_distance("len", "len"), 0);
+        assert_eq!(edit_distance("lenght", "len"), 3);
+        assert_eq!(edit_distance("ignored", "ignore"), 1);
+        assert_eq!(edit_distance("eq", "lt"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn suggests_closest_keyword() {
+        assert_eq!(
+            suggest("lenght 3", &["len", "has_length", "count"]),
+            Some("unknown keyword `lenght`, did you mean `len`?".to_string())
+        );
+        assert_eq!(
+            suggest("ignored 5", &["inconclusive", "ignore"]),
+            Some("unknown keyword `ignored`, did you mean `ignore`?".to_string())
+        );
+    }
+
+    #[test]
+    fn does_not_suggest_unrelated_or_exact_keywords() {
+        assert_eq!(suggest("value", &["len", "empty"]), None);
+        assert_eq!(suggest("len 3", &["len"]), None);
+        assert_eq!(suggest("42", &["len"]), None);
+    }
+
+    #[test]
+    fn mentions_disabled_feature_of_suggested_keyword() {
+        let error = suggest("snapshott", &["snapshot"]).unwrap();
+        if cfg!(feature = "insta") {
+            assert_eq!(
+                error,
+                "unknown keyword `snapshott`, did you mean `snapshot`?"
+            );
+        } else {
+            assert_eq!(
+                error,
+                "unknown keyword `snapshott`, did you mean `snapshot`? note: `snapshot` requires 'insta' feature"
+            );
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::utils::to_snake_case;
+use crate::TokenStream2;
+use proc_macro2::Ident;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Error, Item, ItemMod, Path, Token, Type, TypeParam};
+
+/// Arguments of `#[test_case_suite(S: Storage)]` declaring suite of test cases generic over `S`.
+#[derive(Debug)]
+pub struct TestCaseSuite {
+    param: TypeParam,
+}
+
+/// Arguments of `#[apply_suite(storage_suite, MemStorage, FileStorage)]` instantiating suite for
+/// every listed implementation.
+#[derive(Debug)]
+pub struct ApplySuite {
+    suite: Path,
+    implementations: Punctuated<Type, Token![,]>,
+}
+
+impl Parse for TestCaseSuite {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            param: input.parse()?,
+        })
+    }
+}
+
+impl Parse for ApplySuite {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let suite = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let implementations = Punctuated::parse_separated_nonempty(input)?;
+        let _: Option<Token![,]> = input.parse()?;
+
+        Ok(Self {
+            suite,
+            implementations,
+        })
+    }
+}
+
+impl TestCaseSuite {
+    /// Turns the suite module into `macro_rules!` macro of the same name, which `apply_suite`
+    /// invokes with module name and type of every implementation. Each instance is a module aliasing
+    /// the suite parameter to the implementation and containing copy of the suite items.
+    pub fn render(&self, mut item: ItemMod) -> syn::Result<TokenStream2> {
+        let items = match &mut item.content {
+            Some((_, items)) => items,
+            None => {
+                return Err(Error::new_spanned(
+                    &item,
+                    "test_case_suite requires module with inline content",
+                ))
+            }
+        };
+        qualify_test_case_attributes(items);
+        let name = &item.ident;
+        let attrs = &item.attrs;
+        let param = &self.param.ident;
+        let bounds = &self.param.bounds;
+        let bound_check = (!bounds.is_empty()).then(|| {
+            quote! {
+                const _: fn() = || {
+                    fn implements<T: ?Sized + #bounds>() {}
+                    implements::<#param>();
+                };
+            }
+        });
+
+        Ok(quote! {
+            #[allow(unused_macros)]
+            macro_rules! #name {
+                ($($module:ident = $implementation:ty),* $(,)?) => {
+                    $(
+                        #[cfg(test)]
+                        #(#attrs)*
+                        mod $module {
+                            #[allow(unused_imports)]
+                            use super::*;
+
+                            #[allow(dead_code)]
+                            type #param = $implementation;
+
+                            #bound_check
+
+                            #(#items)*
+                        }
+                    )*
+                };
+            }
+        })
+    }
+}
+
+/// Imports do not reach into modules created by `macro_rules!` expansion through `use super::*`, so
+/// bare `#[test_case]` would resolve to built-in attribute of the same name. Rewrites it (and
+/// `#[case]`, `#[test_matrix]`) to path through `test_case` crate.
+fn qualify_test_case_attributes(items: &mut [Item]) {
+    for item in items {
+        if let Item::Fn(item) = item {
+            for attr in item.attrs.iter_mut() {
+                let ident = match attr.path().get_ident() {
+                    Some(ident) => ident.clone(),
+                    None => continue,
+                };
+                if ident == "test_case" || ident == "case" || ident == "test_matrix" {
+                    let path: Path = parse_quote!(test_case::#ident);
+                    match &mut attr.meta {
+                        syn::Meta::Path(meta) => *meta = path,
+                        syn::Meta::List(meta) => meta.path = path,
+                        syn::Meta::NameValue(meta) => meta.path = path,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ApplySuite {
+    /// Appends invocation of the suite to the module, creating submodule named after every
+    /// implementation, e.g. `MemStorage` gets `mem_storage`.
+    pub fn render(&self, mut item: ItemMod) -> syn::Result<TokenStream2> {
+        let items =
+            match &mut item.content {
+                Some((_, items)) => items,
+                None => return Err(Error::new_spanned(
+                    &item,
+                    "apply_suite requires module with inline content, e.g. `mod storage_suite {}`",
+                )),
+            };
+
+        let mut modules: Vec<Ident> = vec![];
+        for implementation in &self.implementations {
+            let module = match implementation {
+                Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last().map(|segment| {
+                    Ident::new(
+                        &to_snake_case(&segment.ident.to_string()),
+                        segment.ident.span(),
+                    )
+                }),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                Error::new_spanned(
+                    implementation,
+                    "apply_suite can only be used with named types",
+                )
+            })?;
+            if modules.contains(&module) {
+                return Err(Error::new_spanned(
+                    implementation,
+                    format!("suite is already applied to a type generating module `{module}`"),
+                ));
+            }
+            modules.push(module);
+        }
+
+        let suite = &self.suite;
+        let implementations = self.implementations.iter();
+        items.insert(
+            0,
+            parse_quote! {
+                #[allow(unused_imports)]
+                use super::*;
+            },
+        );
+        items.push(parse_quote! {
+            #suite!(#(#modules = #implementations),*);
+        });
+
+        Ok(quote! { #item })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::suite::ApplySuite;
+    use syn::{parse_quote, ItemMod};
+
+    #[test]
+    fn appends_module_per_implementation() {
+        let apply: ApplySuite = parse_quote! { storage_suite, MemStorage, crate::FileStorage };
+        let item: ItemMod = parse_quote! { mod storage_suite {} };
+        let actual = apply.render(item).unwrap().to_string();
+        assert!(
+            actual.contains(
+                &quote::quote! { storage_suite!(mem_storage = MemStorage, file_storage = crate::FileStorage); }
+                    .to_string()
+            ),
+            "{actual}"
+        );
+    }
+
+    #[test]
+    fn rejects_implementations_with_the_same_module() {
+        let apply: ApplySuite = parse_quote! { storage_suite, a::Storage, b::Storage };
+        let item: ItemMod = parse_quote! { mod storage_suite {} };
+        let error = apply.render(item).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "suite is already applied to a type generating module `storage`"
+        );
+    }
+}
@@ -1,33 +1,46 @@
+use crate::arguments::{self, ArgumentsSource, TestCaseDefaults};
 use crate::comment::TestCaseComment;
+use crate::declaration::Declaration;
 use crate::expr::{TestCaseExpression, TestCaseResult};
+use crate::hooks::Hooks;
+use crate::options::{GenericInstance, TestCaseOptions};
 use crate::utils::fmt_syn;
 use proc_macro2::{Span as Span2, TokenStream as TokenStream2};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{parse_quote, Error, Expr, Ident, ItemFn, ReturnType, Token};
+use syn::{
+    parse_quote, Attribute, Error, Expr, Ident, ItemFn, Meta, Path, ReturnType, Token, Type,
+};
 
 #[derive(Debug)]
 pub struct TestCase {
     args: Punctuated<Expr, Token![,]>,
     expression: Option<TestCaseExpression>,
     name: Ident,
+    /// Name substituted for `{case}` in `equals_file` paths, without the expected result.
+    case_name: String,
+    source: TokenStream2,
+    options: TestCaseOptions,
+    hooks: Hooks,
+    defaults: TestCaseDefaults,
+    arguments_source: ArgumentsSource,
+    self_ty: Option<Type>,
 }
 
 impl Parse for TestCase {
     fn parse(input: ParseStream) -> Result<Self, Error> {
-        let args = Punctuated::parse_separated_nonempty_with(input, Expr::parse)?;
-        let expression = (!input.is_empty()).then(|| input.parse()).transpose();
-        let comment = (!input.is_empty()).then(|| input.parse()).transpose();
-        // if both are errors, pick the expression error since it is more likely to be informative.
-        //
-        // TODO(https://github.com/frondeus/test-case/issues/135): avoid Result::ok entirely.
-        let (expression, comment) = match (expression, comment) {
-            (Err(expression), Err(_comment)) => return Err(expression),
-            (expression, comment) => (expression.ok().flatten(), comment.ok().flatten()),
-        };
+        let source = input.cursor().token_stream();
+        let Declaration {
+            args,
+            expression,
+            comment,
+            options,
+        } = input.parse()?;
 
-        Ok(Self::new_from_parsed(args, expression, comment))
+        Ok(Self::new_from_parsed(args, expression, comment)
+            .with_source(source)
+            .with_options(options))
     }
 }
 impl TestCase {
@@ -45,11 +58,22 @@ impl TestCase {
         comment: Option<TestCaseComment>,
     ) -> Self {
         let name = Self::test_case_name_ident(args.iter(), expression.as_ref(), comment.as_ref());
+        let case_name = match comment {
+            Some(comment) => Self::case_name(comment.comment.value()),
+            None => Self::case_name(Self::arguments_name(args.iter())),
+        };
 
         Self {
             args,
             expression,
             name,
+            case_name,
+            source: TokenStream2::new(),
+            options: TestCaseOptions::default(),
+            hooks: Hooks::default(),
+            defaults: TestCaseDefaults::default(),
+            arguments_source: ArgumentsSource::default(),
+            self_ty: None,
         }
     }
 
@@ -60,12 +84,69 @@ impl TestCase {
     ) -> Self {
         let parsed_args = args.into_iter().collect::<Punctuated<Expr, Token![,]>>();
         let name = Self::prefixed_test_case_name(parsed_args.iter(), expression.as_ref(), prefix);
+        let case_name = Self::case_name(format!(
+            "{prefix}_{}",
+            Self::arguments_name(parsed_args.iter())
+        ));
 
         Self {
             args: parsed_args,
             expression,
             name,
+            case_name,
+            source: TokenStream2::new(),
+            options: TestCaseOptions::default(),
+            hooks: Hooks::default(),
+            defaults: TestCaseDefaults::default(),
+            arguments_source: ArgumentsSource::default(),
+            self_ty: None,
+        }
+    }
+
+    /// Attaches tokens of the attribute the test case was declared with, used in failure messages.
+    pub(crate) fn with_source(mut self, source: TokenStream2) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub(crate) fn with_options(mut self, options: TestCaseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets attribute used instead of `#[test]` unless test case declares its own `test_attr`.
+    #[doc(hidden)]
+    pub fn with_default_test_attr(mut self, test_attr: Option<&Meta>) -> Self {
+        if self.options.test_attr.is_none() {
+            self.options.test_attr = test_attr.cloned();
         }
+        self
+    }
+
+    /// Sets setup and teardown functions called around the test case.
+    pub fn with_hooks(mut self, hooks: Hooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Marks the test case as one of cases generated by `#[test_matrix(...)]`, so argument count
+    /// mismatch is reported in terms of matrix axes.
+    pub(crate) fn in_matrix(mut self) -> Self {
+        self.arguments_source = ArgumentsSource::TestMatrix;
+        self
+    }
+
+    /// Sets values of parameters which the test case does not give.
+    pub fn with_defaults(mut self, defaults: TestCaseDefaults) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Sets type of `impl` block the test function is declared in, so the test case calls it as
+    /// associated function or as a method on `receiver`.
+    pub fn with_self_type(mut self, self_ty: Option<&Type>) -> Self {
+        self.self_ty = self_ty.cloned();
+        self
     }
 
     pub fn test_case_name(&self) -> Ident {
@@ -75,11 +156,85 @@ impl TestCase {
         self.name.clone()
     }
 
-    pub fn render(&self, mut item: ItemFn, origin_span: Span2) -> TokenStream2 {
+    pub fn render(&self, item: ItemFn, origin_span: Span2) -> TokenStream2 {
+        match self.options.generic_instances(&item.sig.generics) {
+            Ok(instances) if instances.is_empty() => self.render_instance(item, origin_span, None),
+            Ok(instances) => instances
+                .iter()
+                .map(|instance| self.render_instance(item.clone(), origin_span, Some(instance)))
+                .collect(),
+            Err(err) => err.to_compile_error(),
+        }
+    }
+
+    /// Renders test function calling `item` instantiated with generic arguments of `instance`.
+    fn render_instance(
+        &self,
+        mut item: ItemFn,
+        origin_span: Span2,
+        instance: Option<&GenericInstance>,
+    ) -> TokenStream2 {
         let item_name = item.sig.ident.clone();
-        let arg_values = self.args.iter();
+        let (hooks, setup_value) = match self.hooks.prepare(&item) {
+            Ok(prepared) => prepared,
+            Err(err) => return err.to_compile_error(),
+        };
+        let arg_values = match arguments::call_arguments(
+            &item,
+            &self.args,
+            &self.defaults,
+            setup_value.is_some(),
+            self.arguments_source,
+            origin_span,
+        ) {
+            Ok(arg_values) => arg_values.into_iter().chain(setup_value),
+            Err(err) => return err.to_compile_error(),
+        };
+        let (receiver, callee) = match (&self.self_ty, item.sig.receiver(), &self.options.receiver)
+        {
+            (Some(_), Some(_), Some(receiver)) => (
+                quote! {
+                    #[allow(unused_mut)]
+                    let mut _tc_receiver = #receiver;
+                },
+                quote! { _tc_receiver.#item_name },
+            ),
+            (Some(_), Some(_), None) => {
+                return Error::new(
+                    origin_span,
+                    "test case of a method taking `self` requires `receiver = ...` option",
+                )
+                .to_compile_error()
+            }
+            (_, _, Some(_)) => {
+                return Error::new(
+                    origin_span,
+                    "'receiver' option can only be used with methods taking `self`",
+                )
+                .to_compile_error()
+            }
+            (Some(self_ty), None, None) => (TokenStream2::new(), quote! { <#self_ty>::#item_name }),
+            (None, _, None) => (TokenStream2::new(), quote! { super::#item_name }),
+        };
+        let (callee, name, case_name) = match instance {
+            Some(instance) => {
+                let generic_args = &instance.args;
+                (
+                    quote! { #callee::<#(#generic_args),*> },
+                    crate::utils::escape_test_name(format!("{}{}", self.name, instance.suffix)),
+                    Self::case_name(format!("{}{}", self.case_name, instance.suffix)),
+                )
+            }
+            None => (callee, self.test_case_name(), self.case_name.clone()),
+        };
+        let call_span = if self.args.is_empty() {
+            origin_span
+        } else {
+            crate::utils::span_of(&self.args)
+        };
+        let call = quote_spanned! {call_span=> #callee(#(#arg_values),*) };
         let test_case_name = {
-            let mut test_case_name = self.test_case_name();
+            let mut test_case_name = name.clone();
             test_case_name.set_span(origin_span);
             test_case_name
         };
@@ -93,20 +248,48 @@ impl TestCase {
         attrs.push(parse_quote! { #[allow(clippy::bool_assert_comparison)] });
         attrs.append(&mut item.attrs);
 
-        let (mut signature, body) = if item.sig.asyncness.is_some() {
-            (
-                quote! { async },
-                quote! { let _result = super::#item_name(#(#arg_values),*).await; },
+        let is_async = item.sig.asyncness.is_some();
+        let runtime = self.options.runtime;
+        let test_attr: Attribute = match &self.options.test_attr {
+            Some(test_attr) => parse_quote! { #[#test_attr] },
+            None => parse_quote! { #[::core::prelude::v1::test] },
+        };
+        if let Some(runtime) = runtime {
+            if !is_async {
+                let msg =
+                    format!("'runtime = {runtime}' option can only be used with async functions");
+                return Error::new(origin_span, msg).to_compile_error();
+            }
+        } else if is_async && self.options.test_attr.is_none() && !has_test_attribute(&attrs) {
+            return Error::new(
+                origin_span,
+                "async test case requires a runtime, use `runtime = tokio`, `runtime = async_std` or `runtime = smol` option, add `#[tokio::test]` or `#[async_std::test]` attribute or give other runtime attribute with `test_attr = ...` option",
             )
+            .to_compile_error();
+        }
+
+        let (mut signature, body) = if runtime.is_some() {
+            attrs.insert(0, test_attr);
+            (TokenStream2::new(), quote! { let _result = #call.await; })
+        } else if is_async {
+            if self.options.test_attr.is_some() {
+                attrs.insert(0, test_attr);
+            }
+            (quote! { async }, quote! { let _result = #call.await; })
         } else {
-            attrs.insert(0, parse_quote! { #[::core::prelude::v1::test] });
-            (
-                TokenStream2::new(),
-                quote! { let _result = super::#item_name(#(#arg_values),*); },
-            )
+            attrs.insert(0, test_attr);
+            (TokenStream2::new(), quote! { let _result = #call; })
         };
 
         let expected = if let Some(expr) = self.expression.as_ref() {
+            if expr.is_async() && !is_async {
+                return Error::new(
+                    origin_span,
+                    "'async' assertions with 'using' and 'with' can only be used in async test functions",
+                )
+                .to_compile_error();
+            }
+
             attrs.extend(expr.attributes());
 
             signature.extend(quote! { fn #test_case_name() });
@@ -114,7 +297,7 @@ impl TestCase {
             if let TestCaseResult::Panicking(_) = expr.result {
                 TokenStream2::new()
             } else {
-                expr.assertion()
+                expr.assertion(&name, &case_name, &self.source)
             }
         } else {
             signature.extend(if let ReturnType::Type(_, typ) = item.sig.output {
@@ -126,11 +309,21 @@ impl TestCase {
             quote! { _result }
         };
 
+        let body = quote! {
+            #receiver
+            #hooks
+            #body
+            #expected
+        };
+        let body = match runtime {
+            Some(runtime) => runtime.block_on(body),
+            None => body,
+        };
+
         quote! {
             #(#attrs)*
             #signature {
                 #body
-                #expected
             }
         }
     }
@@ -156,6 +349,19 @@ impl TestCase {
         crate::utils::escape_test_name(full_desc)
     }
 
+    /// Arguments joined with `_`, e.g. `"Rust"_1`.
+    fn arguments_name<'a, I: Iterator<Item = &'a Expr>>(args: I) -> String {
+        args.map(fmt_syn).collect::<Vec<_>>().join("_")
+    }
+
+    /// `desc` escaped like test name but without leading and trailing underscores, e.g. `rust_1`.
+    fn case_name(desc: impl AsRef<str>) -> String {
+        crate::utils::escape_test_name(desc)
+            .to_string()
+            .trim_matches('_')
+            .to_string()
+    }
+
     fn test_case_name_string<'a, I: Iterator<Item = &'a Expr>>(
         args: I,
         expression: Option<&TestCaseExpression>,
@@ -179,3 +385,41 @@ impl TestCase {
             })
     }
 }
+
+/// Whether any of attributes is test attribute of supported async runtime, `#[tokio::test]` or
+/// `#[async_std::test]`. Attributes of other runtimes have to be given with `test_attr = ...` option.
+fn has_test_attribute(attrs: &[Attribute]) -> bool {
+    let runtime_attrs: [Path; 2] = [parse_quote!(tokio::test), parse_quote!(async_std::test)];
+    attrs.iter().any(|attr| {
+        runtime_attrs
+            .iter()
+            .any(|runtime_attr| runtime_attr.segments == attr.path().segments)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_case::has_test_attribute;
+    use syn::{parse_quote, Attribute};
+
+    #[test]
+    fn recognizes_only_supported_runtime_attributes() {
+        let supported: [Attribute; 3] = [
+            parse_quote!(#[tokio::test]),
+            parse_quote!(#[::tokio::test(flavor = "multi_thread")]),
+            parse_quote!(#[async_std::test]),
+        ];
+        for attr in supported {
+            assert!(has_test_attribute(&[attr]));
+        }
+
+        let unsupported: [Attribute; 3] = [
+            parse_quote!(#[test]),
+            parse_quote!(#[my_runtime::test]),
+            parse_quote!(#[rstest::test]),
+        ];
+        for attr in unsupported {
+            assert!(!has_test_attribute(&[attr]));
+        }
+    }
+}
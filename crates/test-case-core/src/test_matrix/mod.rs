@@ -1,14 +1,16 @@
 use std::{iter, mem};
 
-use proc_macro2::{Literal, Span};
+use proc_macro2::{Literal, Span, TokenStream};
 use syn::{
     parse::{Parse, ParseStream},
-    punctuated::Punctuated,
     spanned::Spanned,
-    Expr, ExprLit, ExprRange, Lit, RangeLimits, Token,
+    Expr, ExprLit, ExprRange, Lit, RangeLimits,
 };
 
-use crate::{comment::TestCaseComment, expr::TestCaseExpression, TestCase};
+use crate::{
+    comment::TestCaseComment, declaration::Declaration, expr::TestCaseExpression,
+    options::TestCaseOptions, utils::Errors, TestCase,
+};
 
 mod matrix_product;
 
@@ -17,6 +19,8 @@ pub struct TestMatrix {
     variables: Vec<Vec<Expr>>,
     expression: Option<TestCaseExpression>,
     comment: Option<TestCaseComment>,
+    source: TokenStream,
+    options: TestCaseOptions,
 }
 
 impl TestMatrix {
@@ -27,6 +31,8 @@ impl TestMatrix {
     pub fn cases(&self) -> impl Iterator<Item = TestCase> {
         let expression = self.expression.clone();
         let comment = self.comment.clone();
+        let source = self.source.clone();
+        let options = self.options.clone();
 
         matrix_product::multi_cartesian_product(self.variables.iter().cloned()).map(move |v| {
             if let Some(comment) = comment.clone() {
@@ -38,74 +44,87 @@ impl TestMatrix {
             } else {
                 TestCase::new(v, expression.clone(), None)
             }
+            .with_source(source.clone())
+            .with_options(options.clone())
+            .in_matrix()
         })
     }
 }
 
 impl Parse for TestMatrix {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let args: Punctuated<Expr, Token![,]> = Punctuated::parse_separated_nonempty(input)?;
-
-        let expression = (!input.is_empty()).then(|| input.parse()).transpose();
-        let comment = (!input.is_empty()).then(|| input.parse()).transpose();
-        // if both are errors, pick the expression error since it is more likely to be informative.
-        //
-        // TODO(https://github.com/frondeus/test-case/issues/135): avoid Result::ok entirely.
-        let (expression, comment) = match (expression, comment) {
-            (Err(expression), Err(_comment)) => return Err(expression),
-            (expression, comment) => (expression.ok().flatten(), comment.ok().flatten()),
-        };
+        let source = input.cursor().token_stream();
+        let Declaration {
+            args,
+            expression,
+            comment,
+            options,
+        } = input.parse()?;
 
         let mut matrix = TestMatrix {
             expression,
             comment,
+            source,
+            options,
             ..Default::default()
         };
 
+        let mut errors = Errors::default();
         for arg in args {
-            let values: Vec<Expr> = match &arg {
-                Expr::Array(v) => v.elems.iter().cloned().collect(),
-                Expr::Tuple(v) => v.elems.iter().cloned().collect(),
-                Expr::Range(ExprRange {
-                    start, limits, end, ..
-                }) => {
-                    let start = isize_from_range_expr(limits.span(), start.as_deref())?;
-                    let end = isize_from_range_expr(limits.span(), end.as_deref())?;
-                    let range: Box<dyn Iterator<Item = isize>> = match limits {
-                        RangeLimits::HalfOpen(_) => Box::from(start..end),
-                        RangeLimits::Closed(_) => Box::from(start..=end),
-                    };
-                    range
-                        .map(|n| {
-                            let mut lit = Lit::new(Literal::isize_unsuffixed(n));
-                            lit.set_span(arg.span());
-                            Expr::from(ExprLit { lit, attrs: vec![] })
-                        })
-                        .collect()
-                }
-                v => iter::once(v.clone()).collect(),
-            };
-
-            let mut value_literal_type = None;
-            for expr in &values {
-                if let Expr::Lit(ExprLit { lit, .. }) = expr {
-                    let first_literal_type =
-                        *value_literal_type.get_or_insert_with(|| mem::discriminant(lit));
-                    if first_literal_type != mem::discriminant(lit) {
-                        return Err(syn::Error::new(
-                            lit.span(),
-                            "All literal values must be of the same type",
-                        ));
-                    }
-                }
+            match axis_values(&arg) {
+                Ok(values) => matrix.push_argument(values),
+                Err(error) => errors.push(error),
             }
-            matrix.push_argument(values);
         }
+        errors.finish()?;
 
         Ok(matrix)
     }
 }
 
+/// Values of a single matrix axis: elements of an array or tuple, numbers of a range or the
+/// expression itself.
+fn axis_values(arg: &Expr) -> syn::Result<Vec<Expr>> {
+    let values: Vec<Expr> = match arg {
+        Expr::Array(v) => v.elems.iter().cloned().collect(),
+        Expr::Tuple(v) => v.elems.iter().cloned().collect(),
+        Expr::Range(ExprRange {
+            start, limits, end, ..
+        }) => {
+            let start = isize_from_range_expr(limits.span(), start.as_deref())?;
+            let end = isize_from_range_expr(limits.span(), end.as_deref())?;
+            let range: Box<dyn Iterator<Item = isize>> = match limits {
+                RangeLimits::HalfOpen(_) => Box::from(start..end),
+                RangeLimits::Closed(_) => Box::from(start..=end),
+            };
+            range
+                .map(|n| {
+                    let mut lit = Lit::new(Literal::isize_unsuffixed(n));
+                    lit.set_span(arg.span());
+                    Expr::from(ExprLit { lit, attrs: vec![] })
+                })
+                .collect()
+        }
+        v => iter::once(v.clone()).collect(),
+    };
+
+    let mut value_literal_type = None;
+    for expr in &values {
+        if let Expr::Lit(ExprLit { lit, .. }) = expr {
+            let first_literal_type =
+                *value_literal_type.get_or_insert_with(|| mem::discriminant(lit));
+            if first_literal_type != mem::discriminant(lit) {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "All literal values must be of the same type",
+                ));
+            }
+        }
+    }
+
+    Ok(values)
+}
+
 fn isize_from_range_expr(limits_span: Span, expr: Option<&Expr>) -> syn::Result<isize> {
     match expr {
         Some(Expr::Lit(ExprLit {
@@ -1,5 +1,6 @@
-use proc_macro2::{Ident, Span};
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::ToTokens;
+use syn::spanned::Spanned;
 
 pub fn escape_test_name(input: impl AsRef<str>) -> Ident {
     if input.as_ref().is_empty() {
@@ -31,10 +32,69 @@ pub fn escape_test_name(input: impl AsRef<str>) -> Ident {
     Ident::new(&ident, Span::call_site())
 }
 
+/// Span pointing at `tokens` but resolving identifiers at the macro call site, so that generated code
+/// refers to its own variables (e.g. `_result`) even when `tokens` come from `macro_rules!` expansion.
+pub fn span_of(tokens: &impl Spanned) -> Span {
+    Span::call_site().located_at(tokens.span())
+}
+
 pub fn fmt_syn(syn: &(impl ToTokens + Clone)) -> String {
     syn.clone().into_token_stream().to_string()
 }
 
+/// `TempDb` -> `temp_db`, `HTTPServer` -> `http_server`
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (idx, c) in chars.iter().enumerate() {
+        let prev = idx.checked_sub(1).map(|prev| chars[prev]);
+        let next = chars.get(idx + 1);
+        let word_start = c.is_uppercase()
+            && match prev {
+                Some(prev) => {
+                    prev.is_lowercase()
+                        || prev.is_ascii_digit()
+                        || (prev.is_uppercase()
+                            && matches!(next, Some(next) if next.is_lowercase()))
+                }
+                None => false,
+            };
+        if word_start {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Errors of independent parts of the input (e.g. attributes of a function), accumulated so that all
+/// of them are reported at once.
+#[derive(Default)]
+pub struct Errors(Option<syn::Error>);
+
+impl Errors {
+    pub fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn finish(self) -> syn::Result<()> {
+        self.0.map_or(Ok(()), Err)
+    }
+
+    pub fn into_compile_error(self) -> TokenStream2 {
+        self.0
+            .map(syn::Error::into_compile_error)
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +162,16 @@ mod tests {
             );
         }
     }
+
+    mod to_snake_case {
+        use super::*;
+
+        #[test]
+        fn splits_words_on_case_change() {
+            assert_eq!(to_snake_case("Storage"), "storage");
+            assert_eq!(to_snake_case("MemStorage"), "mem_storage");
+            assert_eq!(to_snake_case("HTTPServer"), "http_server");
+            assert_eq!(to_snake_case("Sha256Hash"), "sha256_hash");
+        }
+    }
 }
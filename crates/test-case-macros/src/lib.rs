@@ -2,13 +2,17 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 
-use proc_macro2::Span as Span2;
-use syn::{parse_macro_input, ItemFn, Path};
+use proc_macro2::{Span as Span2, TokenStream as TokenStream2};
+use syn::parse::Parse;
+use syn::{parse_macro_input, Ident, ImplItem, ItemFn, ItemImpl, ItemMod, Meta, Path, Type};
 
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::parse_quote;
 use syn::spanned::Spanned;
-use test_case_core::{TestCase, TestMatrix};
+use test_case_core::{
+    remove_fixture_attributes, to_snake_case, ApplySuite, CaseSets, Errors, Hooks, TestCase,
+    TestCaseDefaults, TestCaseSuite, TestMatrix,
+};
 
 /// Generates tests for given set of data
 ///
@@ -21,19 +25,40 @@ use test_case_core::{TestCase, TestMatrix};
 ///
 ///  When _expected result_ is provided, it is compared against the actual value generated with _test body_ using `assert_eq!`.
 /// _Test cases_ that don't provide _expected result_ should contain custom assertions within _test body_ or return `Result` similar to `#[test]` macro.
+///
+/// Generated cases are marked with `#[test]` unless another attribute is given, either per case with
+/// `test_attr` option (`#[test_case(1 ; test_attr = my_harness::test)]`) or for all cases with
+/// `#[test_case_attr(my_harness::test)]` placed below `#[test_case]`.
+///
+/// Function level `#[test_case_setup(make_fixture)]` calls `make_fixture()` before every case and
+/// passes returned value as the last argument of test body. `#[test_case_teardown(cleanup)]` calls
+/// `cleanup` after every case, even if it panics. When both are declared, test body takes the value
+/// by reference instead (its last parameter has to be `&T` or `&mut T`) and `cleanup` receives the
+/// value itself once the case is done, so temporary directories or child processes can be used as
+/// fixtures. Note that `cleanup` panicking while a failed case unwinds aborts the whole test binary.
+///
+/// Parameters marked with `#[fixture]` are constructed for every case by calling function named after
+/// them (`#[fixture] db: TempDb` calls `db()`), case arguments are passed to remaining parameters in order.
+///
+/// Arguments can be also given by parameter name, in any order after positional ones:
+/// `#[test_case(width = 10, height = 2 => 20)]`. Function level `#[test_case_defaults(height = 1)]`
+/// gives values of parameters for cases which do not list them.
 #[proc_macro_attribute]
 pub fn test_case(args: TokenStream, input: TokenStream) -> TokenStream {
-    let test_case = parse_macro_input!(args as TestCase);
     let mut item = parse_macro_input!(input as ItemFn);
+    if let Some(deferred) = defer_to_case_set(quote!(test_case::test_case), &args, &mut item) {
+        return deferred;
+    }
+    let mut errors = Errors::default();
+    let mut test_cases = vec![];
 
-    let mut test_cases = vec![(test_case, Span2::call_site())];
-
-    match expand_additional_test_case_macros(&mut item) {
-        Ok(cases) => test_cases.extend(cases),
-        Err(err) => return err.into_compile_error().into(),
+    match syn::parse::<TestCase>(args) {
+        Ok(test_case) => test_cases.push((test_case, Span2::call_site())),
+        Err(err) => errors.push(err),
     }
+    test_cases.extend(expand_additional_test_case_macros(&mut item, &mut errors).0);
 
-    render_test_cases(&test_cases, item)
+    render_test_cases(test_cases, item, errors)
 }
 
 /// Generates tests for the cartesian product of a given set of data
@@ -50,24 +75,266 @@ pub fn test_case(args: TokenStream, input: TokenStream) -> TokenStream {
 /// macro but are applied to every case generated by `#[test_matrix(...)]`.
 #[proc_macro_attribute]
 pub fn test_matrix(args: TokenStream, input: TokenStream) -> TokenStream {
-    let matrix = parse_macro_input!(args as TestMatrix);
     let mut item = parse_macro_input!(input as ItemFn);
+    if let Some(deferred) = defer_to_case_set(quote!(test_case::test_matrix), &args, &mut item) {
+        return deferred;
+    }
+    let mut errors = Errors::default();
+    let mut test_cases = vec![];
+
+    match syn::parse::<TestMatrix>(args) {
+        Ok(matrix) => test_cases.extend(expand_test_matrix(&matrix, Span2::call_site())),
+        Err(err) => errors.push(err),
+    }
+    test_cases.extend(expand_additional_test_case_macros(&mut item, &mut errors).0);
+
+    render_test_cases(test_cases, item, errors)
+}
+
+/// Declares named sets of test cases reusable across test functions
+///
+/// Cases are separated with `,` and written exactly as they would be inside `#[test_case(...)]`,
+/// including expected result, comment and options. Case taking several arguments has to be wrapped in
+/// parentheses as a whole, e.g. `(1, 2 => 3 ; "sum")`. Sets are applied with `#[test_case_set(name)]`:
+///
+/// ```ignore
+/// case_set!(tricky_inputs: ["", "\0", "ünïcödé" ; "unicode"]);
+///
+/// #[test_case_set(tricky_inputs)]
+/// #[test_case("plain")]
+/// fn roundtrips(input: &str) {
+///     assert_eq!(decode(&encode(input)), input)
+/// }
+/// ```
+///
+/// Multiple sets can be declared at once, separated with `;`. Under the hood every set becomes
+/// `macro_rules!` macro of the same name, so it has to be declared before it is used.
+#[proc_macro]
+pub fn case_set(input: TokenStream) -> TokenStream {
+    let sets = parse_macro_input!(input as CaseSets);
+
+    sets.render().into()
+}
+
+/// Adds test cases of set declared with `case_set!` to the test function
+///
+/// Can be mixed with `#[test_case(...)]` and `#[test_matrix(...)]` attributes in any order.
+#[proc_macro_attribute]
+pub fn test_case_set(args: TokenStream, input: TokenStream) -> TokenStream {
+    let set = parse_macro_input!(args as Path);
+    let input = TokenStream2::from(input);
+
+    quote! {
+        #set! {
+            #input
+        }
+    }
+    .into()
+}
 
-    let mut test_cases = expand_test_matrix(&matrix, Span2::call_site());
+/// Set of cases can be expanded only by invoking its `macro_rules!` callback, so when test function
+/// still has `#[test_case_set(...)]` attribute, the attribute being expanded (`attr_path(args)`) is put
+/// back below it and rendering is left to the last test case attribute.
+fn defer_to_case_set(
+    attr_path: TokenStream2,
+    args: &TokenStream,
+    item: &mut ItemFn,
+) -> Option<TokenStream> {
+    let legal_case_set_names: [Path; 2] = [
+        parse_quote!(test_case_set),
+        parse_quote!(test_case::test_case_set),
+    ];
+    let idx = item
+        .attrs
+        .iter()
+        .position(|attr| legal_case_set_names.contains(attr.path()))?;
+    let case_set = item.attrs.remove(idx);
+    let args = TokenStream2::from(args.clone());
+
+    Some(
+        quote! {
+            #case_set
+            #[#attr_path(#args)]
+            #item
+        }
+        .into(),
+    )
+}
 
-    match expand_additional_test_case_macros(&mut item) {
-        Ok(cases) => test_cases.extend(cases),
-        Err(err) => return err.into_compile_error().into(),
+/// Generates tests for methods and associated functions of `impl` block
+///
+/// `#[test_case(...)]` and `#[test_matrix(...)]` attributes placed on functions inside the block are
+/// rendered into sibling modules named after the type and the function (`fn open` of `impl TempDb`
+/// produces `mod temp_db_open`), since modules cannot be declared inside `impl` blocks. Functions
+/// without test cases get no module, so the type can have several `#[test_case_impl]` blocks.
+/// Methods keep their other attributes (docs, `#[inline]`, ...) and their `#[cfg]` is copied to the
+/// module. Associated functions are called as `TempDb::f(...)`,
+/// methods taking `self` require `receiver = ...` option giving the instance they are called on:
+///
+/// ```ignore
+/// #[test_case_impl]
+/// impl Counter {
+///     #[test_case(2 => 3 ; receiver = Counter::new(1))]
+///     fn add(&self, n: u32) -> u32 {
+///         self.0 + n
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test_case_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(input as ItemImpl);
+
+    match expand_test_case_impl(TokenStream2::from(args), &mut item) {
+        Ok(test_modules) => quote! {
+            #item
+
+            #test_modules
+        }
+        .into(),
+        Err(err) => err.into_compile_error().into(),
     }
+}
 
-    render_test_cases(&test_cases, item)
+/// Declares suite of test cases generic over implementation of a trait
+///
+/// Module annotated with `#[test_case_suite(S: Storage)]` is not compiled on its own, instead its
+/// items are instantiated by `#[apply_suite(...)]` once per implementation with `S` being a type
+/// alias to it. Under the hood the suite becomes `macro_rules!` macro of the same name, so it has to
+/// be declared before `#[apply_suite(...)]` in the same file or in a `#[macro_use]` module.
+///
+/// ```ignore
+/// #[test_case_suite(S: Storage + Default)]
+/// mod storage_suite {
+///     #[test_case("key" => Some("value".to_string()))]
+///     fn stores(key: &str) -> Option<String> {
+///         let mut storage = S::default();
+///         storage.put(key, "value");
+///         storage.get(key)
+///     }
+/// }
+///
+/// #[apply_suite(storage_suite, MemStorage, FileStorage)]
+/// mod storage_suite {}
+/// ```
+///
+/// Generated tests have paths like `storage_suite::mem_storage::stores::_key_expects_some_value_to_string_`.
+#[proc_macro_attribute]
+pub fn test_case_suite(args: TokenStream, input: TokenStream) -> TokenStream {
+    let suite = parse_macro_input!(args as TestCaseSuite);
+    let item = parse_macro_input!(input as ItemMod);
+
+    suite
+        .render(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Instantiates suite declared with `#[test_case_suite(...)]` for every listed implementation
+///
+/// Must be placed on a module with inline content, submodule named after every implementation
+/// (`MemStorage` becomes `mem_storage`) is appended to it.
+#[proc_macro_attribute]
+pub fn apply_suite(args: TokenStream, input: TokenStream) -> TokenStream {
+    let apply = parse_macro_input!(args as ApplySuite);
+    let item = parse_macro_input!(input as ItemMod);
+
+    apply
+        .render(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_test_case_impl(args: TokenStream2, item: &mut ItemImpl) -> syn::Result<TokenStream2> {
+    if !args.is_empty() {
+        return Err(syn::Error::new_spanned(
+            args,
+            "test_case_impl does not accept arguments",
+        ));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "test_case_impl cannot be used on generic impl blocks",
+        ));
+    }
+    let type_name = match item.self_ty.as_ref() {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last().map(|segment| {
+            Ident::new(
+                &to_snake_case(&segment.ident.to_string()),
+                segment.ident.span(),
+            )
+        }),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new_spanned(
+            &item.self_ty,
+            "test_case_impl can only be used on impl blocks of named types",
+        )
+    })?;
+
+    let mut errors = Errors::default();
+    let mut test_modules = vec![];
+    for impl_item in item.items.iter_mut() {
+        let method = match impl_item {
+            ImplItem::Fn(method) => method,
+            _ => continue,
+        };
+        let mut item_fn = ItemFn {
+            attrs: method.attrs.clone(),
+            vis: method.vis.clone(),
+            sig: method.sig.clone(),
+            block: Box::new(method.block.clone()),
+        };
+
+        let (test_cases, found) = expand_additional_test_case_macros(&mut item_fn, &mut errors);
+        if !found {
+            continue;
+        }
+        let mod_name = format_ident!(
+            "{}_{}",
+            type_name,
+            item_fn.sig.ident,
+            span = type_name.span()
+        );
+        // Methods overloaded on `#[cfg]` get modules of the same name, only one may survive.
+        let cfg_attrs: Vec<_> = item_fn
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .cloned()
+            .collect();
+        match render_test_module(test_cases, &mut item_fn, Some(&item.self_ty), mod_name) {
+            Ok(test_module) => test_modules.push(quote! { #(#cfg_attrs)* #test_module }),
+            Err(err) => errors.push(err),
+        }
+
+        // Unlike free functions, methods are part of the type's API, so only attributes meant
+        // for test cases are stripped, the rest (docs, `#[cfg]`, `#[inline]`, ...) is kept.
+        strip_test_case_attributes(&mut item_fn);
+        method.attrs = item_fn.attrs;
+        method.sig = item_fn.sig;
+    }
+    let errors = errors.into_compile_error();
+
+    Ok(quote! {
+        #(#test_modules)*
+
+        #errors
+    })
 }
 
 fn expand_test_matrix(matrix: &TestMatrix, span: Span2) -> Vec<(TestCase, Span2)> {
     matrix.cases().map(|c| (c, span)).collect()
 }
 
-fn expand_additional_test_case_macros(item: &mut ItemFn) -> syn::Result<Vec<(TestCase, Span2)>> {
+/// Parses remaining `test_case` and `test_matrix` attributes of the function and removes them.
+/// Errors of malformed attributes are pushed to `errors`, so that cases of the other attributes are
+/// still rendered. Returned flag tells whether any such attribute was found, even a malformed one.
+fn expand_additional_test_case_macros(
+    item: &mut ItemFn,
+    errors: &mut Errors,
+) -> (Vec<(TestCase, Span2)>, bool) {
     let mut additional_cases = vec![];
     let mut attrs_to_remove = vec![];
     let legal_test_case_names: [Path; 4] = [
@@ -83,48 +350,126 @@ fn expand_additional_test_case_macros(item: &mut ItemFn) -> syn::Result<Vec<(Tes
 
     for (idx, attr) in item.attrs.iter().enumerate() {
         if legal_test_case_names.contains(attr.path()) {
-            let test_case = match attr.parse_args::<TestCase>() {
-                Ok(test_case) => test_case,
-                Err(err) => {
-                    return Err(syn::Error::new(
-                        attr.span(),
-                        format!("cannot parse test_case arguments: {err}"),
-                    ))
-                }
-            };
-            additional_cases.push((test_case, attr.span()));
+            match attr.parse_args::<TestCase>() {
+                Ok(test_case) => additional_cases.push((test_case, attr.span())),
+                Err(err) => errors.push(err),
+            }
             attrs_to_remove.push(idx);
         } else if legal_test_matrix_names.contains(attr.path()) {
-            let test_matrix = match attr.parse_args::<TestMatrix>() {
-                Ok(test_matrix) => test_matrix,
-                Err(err) => {
-                    return Err(syn::Error::new(
-                        attr.span(),
-                        format!("cannot parse test_matrix arguments: {err}"),
-                    ))
+            match attr.parse_args::<TestMatrix>() {
+                Ok(test_matrix) => {
+                    additional_cases.extend(expand_test_matrix(&test_matrix, attr.span()))
                 }
-            };
-            additional_cases.extend(expand_test_matrix(&test_matrix, attr.span()));
+                Err(err) => errors.push(err),
+            }
             attrs_to_remove.push(idx);
         }
     }
 
+    let found = !attrs_to_remove.is_empty();
     for i in attrs_to_remove.into_iter().rev() {
         item.attrs.swap_remove(i);
     }
 
-    Ok(additional_cases)
+    (additional_cases, found)
 }
 
-#[allow(unused_mut)]
-fn render_test_cases(test_cases: &[(TestCase, Span2)], mut item: ItemFn) -> TokenStream {
-    let mut rendered_test_cases = vec![];
+/// Removes function level attribute with one of given `names` (e.g. `#[test_case_attr(...)]`) from
+/// the function and returns its parsed arguments.
+fn extract_fn_attr<T: Parse>(item: &mut ItemFn, names: &[Path]) -> syn::Result<Option<T>> {
+    let label = names[0]
+        .get_ident()
+        .expect("first name is a bare identifier");
 
-    for (test_case, span) in test_cases {
-        rendered_test_cases.push(test_case.render(item.clone(), *span));
+    let mut value = None;
+    for attr in &item.attrs {
+        if names.contains(attr.path()) {
+            if value.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    format!("{label} can be declared only once per function"),
+                ));
+            }
+            value = Some(attr.parse_args::<T>().map_err(|err| {
+                syn::Error::new(
+                    attr.span(),
+                    format!("cannot parse {label} arguments: {err}"),
+                )
+            })?);
+        }
+    }
+    item.attrs.retain(|attr| !names.contains(attr.path()));
+
+    Ok(value)
+}
+
+/// Removes every attribute consumed by test cases from the function, including those left behind
+/// when parsing of the function's test cases failed halfway.
+fn strip_test_case_attributes(item: &mut ItemFn) {
+    let names: [Path; 16] = [
+        parse_quote!(test_case),
+        parse_quote!(test_case::test_case),
+        parse_quote!(case),
+        parse_quote!(test_case::case),
+        parse_quote!(test_matrix),
+        parse_quote!(test_case::test_matrix),
+        parse_quote!(test_case_attr),
+        parse_quote!(test_case::test_case_attr),
+        parse_quote!(test_case_setup),
+        parse_quote!(test_case::test_case_setup),
+        parse_quote!(test_case_teardown),
+        parse_quote!(test_case::test_case_teardown),
+        parse_quote!(test_case_defaults),
+        parse_quote!(test_case::test_case_defaults),
+        parse_quote!(test_case_set),
+        parse_quote!(test_case::test_case_set),
+    ];
+    item.attrs.retain(|attr| !names.contains(attr.path()));
+    remove_fixture_attributes(item);
+}
+
+/// Removes `#[test_case_setup(...)]` and `#[test_case_teardown(...)]` from the function and returns
+/// hooks they declare.
+fn extract_hooks(item: &mut ItemFn) -> syn::Result<Hooks> {
+    let setup = extract_fn_attr(
+        item,
+        &[
+            parse_quote!(test_case_setup),
+            parse_quote!(test_case::test_case_setup),
+        ],
+    )?;
+    let teardown = extract_fn_attr(
+        item,
+        &[
+            parse_quote!(test_case_teardown),
+            parse_quote!(test_case::test_case_teardown),
+        ],
+    )?;
+
+    Ok(Hooks { setup, teardown })
+}
+
+fn render_test_cases(
+    test_cases: Vec<(TestCase, Span2)>,
+    mut item: ItemFn,
+    mut errors: Errors,
+) -> TokenStream {
+    if let Some(receiver) = item.sig.receiver() {
+        errors.push(syn::Error::new_spanned(
+            receiver,
+            "test cases of methods require `#[test_case_impl]` attribute on the impl block",
+        ));
+        return errors.into_compile_error().into();
     }
 
     let mod_name = item.sig.ident.clone();
+    let test_module = match render_test_module(test_cases, &mut item, None, mod_name) {
+        Ok(test_module) => test_module,
+        Err(err) => {
+            errors.push(err);
+            return errors.into_compile_error().into();
+        }
+    };
 
     // We don't want any external crate to alter main fn code, we are passing attributes to each sub-function anyway
     item.attrs.retain(|attr| {
@@ -133,11 +478,67 @@ fn render_test_cases(test_cases: &[(TestCase, Span2)], mut item: ItemFn) -> Toke
             .map(|ident| ident == "allow")
             .unwrap_or(false)
     });
+    // Function is still emitted when some of its attributes are malformed, so that errors are
+    // reported only once, lints about it would just add noise to these errors.
+    let allow_unused = if errors.is_empty() {
+        TokenStream2::new()
+    } else {
+        quote! { #[allow(unused)] }
+    };
+    let errors = errors.into_compile_error();
 
     let output = quote! {
         #[allow(unused_attributes)]
+        #allow_unused
         #item
 
+        #test_module
+
+        #errors
+    };
+
+    output.into()
+}
+
+/// Renders module `mod_name` with all test cases of the function and strips attributes meant for
+/// test cases from the function itself.
+fn render_test_module(
+    test_cases: Vec<(TestCase, Span2)>,
+    item: &mut ItemFn,
+    self_ty: Option<&Type>,
+    mod_name: Ident,
+) -> syn::Result<TokenStream2> {
+    let test_attr = extract_fn_attr::<Meta>(
+        item,
+        &[
+            parse_quote!(test_case_attr),
+            parse_quote!(test_case::test_case_attr),
+        ],
+    )?;
+    let hooks = extract_hooks(item)?;
+    let defaults = extract_fn_attr::<TestCaseDefaults>(
+        item,
+        &[
+            parse_quote!(test_case_defaults),
+            parse_quote!(test_case::test_case_defaults),
+        ],
+    )?
+    .unwrap_or_default();
+
+    let mut rendered_test_cases = vec![];
+
+    for (test_case, span) in test_cases {
+        let test_case = test_case
+            .with_default_test_attr(test_attr.as_ref())
+            .with_hooks(hooks.clone())
+            .with_defaults(defaults.clone())
+            .with_self_type(self_ty);
+        rendered_test_cases.push(test_case.render(item.clone(), span));
+    }
+
+    remove_fixture_attributes(item);
+
+    Ok(quote! {
         #[cfg(test)]
         mod #mod_name {
             #[allow(unused_imports)]
@@ -145,7 +546,5 @@ fn render_test_cases(test_cases: &[(TestCase, Span2)], mut item: ItemFn) -> Toke
 
             #(#rendered_test_cases)*
         }
-    };
-
-    output.into()
+    })
 }
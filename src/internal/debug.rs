@@ -0,0 +1,28 @@
+//! `Debug` representation of a value which doesn't require the value to implement `Debug`.
+//!
+//! Generated code has to bring both [`ViaDebug`] and [`ViaNoDebug`] into scope and call
+//! `(&DebugProbe(&value)).debug_string()`.
+
+use std::fmt::Debug;
+
+pub struct DebugProbe<'a, T: ?Sized>(pub &'a T);
+
+pub trait ViaDebug {
+    fn debug_string(&self) -> String;
+}
+
+impl<T: Debug + ?Sized> ViaDebug for DebugProbe<'_, T> {
+    fn debug_string(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+pub trait ViaNoDebug {
+    fn debug_string(&self) -> String;
+}
+
+impl<T: ?Sized> ViaNoDebug for &DebugProbe<'_, T> {
+    fn debug_string(&self) -> String {
+        format!("<{} does not implement Debug>", std::any::type_name::<T>())
+    }
+}
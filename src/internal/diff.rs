@@ -0,0 +1,183 @@
+//! Line-by-line diff of two texts, used by `equals_file` expectation and by `assert_eq!`
+//! replacement printing diff of pretty `Debug` output (requires `pretty-diff` feature).
+
+#[cfg(feature = "pretty-diff")]
+use std::fmt::Debug;
+use std::fmt::Write;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+#[cfg(feature = "pretty-diff")]
+#[track_caller]
+pub fn assert_eq<L, R>(left: &L, right: &R, test_case_name: &str, declaration: &str)
+where
+    L: PartialEq<R> + Debug + ?Sized,
+    R: Debug + ?Sized,
+{
+    if left != right {
+        panic!(
+            "assertion `left == right` failed in test case `{}`\n  declared as: {}\n\nDiff < left / right > :\n{}",
+            test_case_name,
+            declaration,
+            diff(&format!("{left:#?}"), &format!("{right:#?}"))
+        )
+    }
+}
+
+enum Line<'a> {
+    Both(&'a str),
+    Left(&'a str),
+    Right(&'a str),
+}
+
+/// Lines present only in `left` are prefixed with `<`, lines present only in `right` with `>`.
+pub fn diff(left: &str, right: &str) -> String {
+    render(left, right, colored())
+}
+
+/// `std::io::IsTerminal` is not available on supported Rust versions, so colors are picked from
+/// environment: `NO_COLOR` disables them, `CLICOLOR_FORCE` forces them and otherwise they are used
+/// only when `TERM` names a terminal which is not `dumb`.
+fn colored() -> bool {
+    let set = |name| std::env::var_os(name).map_or(false, |v| !v.is_empty() && v != "0");
+    if set("NO_COLOR") {
+        false
+    } else if set("CLICOLOR_FORCE") {
+        true
+    } else {
+        std::env::var_os("TERM").map_or(false, |term| !term.is_empty() && term != "dumb")
+    }
+}
+
+fn render(left: &str, right: &str, colored: bool) -> String {
+    let mut output = String::new();
+
+    for line in diff_lines(left, right) {
+        let _ = match line {
+            Line::Both(line) => writeln!(output, " {line}"),
+            Line::Left(line) if colored => writeln!(output, "{RED}<{line}{RESET}"),
+            Line::Left(line) => writeln!(output, "<{line}"),
+            Line::Right(line) if colored => writeln!(output, "{GREEN}>{line}{RESET}"),
+            Line::Right(line) => writeln!(output, ">{line}"),
+        };
+    }
+
+    output
+}
+
+/// Largest LCS table built by [`diff_lines`], bigger inputs are diffed coarsely.
+const MAX_LCS_CELLS: usize = 1_000_000;
+
+/// Diff based on longest common subsequence of lines. Common prefix and suffix are matched
+/// directly; if the rest is too large for LCS table, it is reported as removed and added as a whole.
+fn diff_lines<'a>(left: &'a str, right: &'a str) -> Vec<Line<'a>> {
+    let left: Vec<&str> = left.lines().collect();
+    let right: Vec<&str> = right.lines().collect();
+
+    let prefix = left
+        .iter()
+        .zip(&right)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = left[prefix..]
+        .iter()
+        .rev()
+        .zip(right[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+
+    let mut lines: Vec<Line> = left[..prefix].iter().map(|line| Line::Both(line)).collect();
+    let (middle_left, middle_right) = (
+        &left[prefix..left.len() - suffix],
+        &right[prefix..right.len() - suffix],
+    );
+    if (middle_left.len() + 1).saturating_mul(middle_right.len() + 1) <= MAX_LCS_CELLS {
+        lcs_diff(middle_left, middle_right, &mut lines);
+    } else {
+        lines.extend(middle_left.iter().map(|line| Line::Left(line)));
+        lines.extend(middle_right.iter().map(|line| Line::Right(line)));
+    }
+    lines.extend(
+        left[left.len() - suffix..]
+            .iter()
+            .map(|line| Line::Both(line)),
+    );
+
+    lines
+}
+
+fn lcs_diff<'a>(left: &[&'a str], right: &[&'a str], lines: &mut Vec<Line<'a>>) {
+    // lcs[i][j] is the length of the longest common subsequence of left[i..] and right[j..]
+    let mut lcs = vec![vec![0usize; right.len() + 1]; left.len() + 1];
+    for i in (0..left.len()).rev() {
+        for j in (0..right.len()).rev() {
+            lcs[i][j] = if left[i] == right[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if left[i] == right[j] {
+            lines.push(Line::Both(left[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(Line::Left(left[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Right(right[j]));
+            j += 1;
+        }
+    }
+    lines.extend(left[i..].iter().map(|line| Line::Left(line)));
+    lines.extend(right[j..].iter().map(|line| Line::Right(line)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_removed_and_added_lines() {
+        assert_eq!(
+            render("a\nb\nc\nd", "a\nc\nx\nd", false),
+            " a\n<b\n c\n>x\n d\n"
+        );
+    }
+
+    #[test]
+    fn colors_removed_and_added_lines() {
+        assert_eq!(
+            render("a\nb", "a\nc", true),
+            " a\n\x1b[31m<b\x1b[0m\n\x1b[32m>c\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn reports_whole_texts_when_nothing_is_common() {
+        assert_eq!(render("a\nb", "c", false), "<a\n<b\n>c\n");
+        assert_eq!(render("", "a", false), ">a\n");
+    }
+
+    #[test]
+    fn diffs_large_inputs_coarsely() {
+        let left: String = (0..2000).map(|i| format!("{i}\n")).collect();
+        let right: String = (0..2000).map(|i| format!("{}\n", i * 2 + 1)).collect();
+        let left = format!("first\n{left}last");
+        let right = format!("first\n{right}last");
+
+        let output = render(&left, &right, false);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 4002);
+        assert_eq!(lines[0], " first");
+        assert_eq!(lines[1], "<0");
+        assert_eq!(lines[2001], ">1");
+        assert_eq!(lines[4001], " last");
+    }
+}
@@ -0,0 +1,166 @@
+//! Golden file comparison (`equals_file`).
+//!
+//! Results implementing `AsRef<str>` are compared as they are and every other result is rendered
+//! with `Display`. The choice is made at the call site with autoref-based specialization:
+//! generated code has to bring both [`StrContent`] and [`DisplayContent`] into scope and call
+//! `(&Content(&result)).content()`.
+
+use std::fmt::Display;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Name of environment variable which, when set to `1`, makes failing comparisons overwrite
+/// golden files with actual content instead of panicking.
+pub const BLESS_VAR: &str = "TEST_CASE_BLESS";
+
+pub struct Content<'a, T: ?Sized>(pub &'a T);
+
+pub trait StrContent {
+    fn content(&self) -> String;
+}
+
+impl<'a, T: AsRef<str> + ?Sized> StrContent for Content<'a, T> {
+    fn content(&self) -> String {
+        self.0.as_ref().to_string()
+    }
+}
+
+pub trait DisplayContent {
+    fn content(&self) -> String;
+}
+
+impl<'a, T: Display + ?Sized> DisplayContent for &Content<'a, T> {
+    fn content(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Compares `actual` with content of the file at `path` (relative to `manifest_dir`).
+#[track_caller]
+pub fn assert_file_eq(actual: &str, manifest_dir: &str, path: &str, test_case_name: &str) {
+    let bless = std::env::var(BLESS_VAR).map_or(false, |v| v == "1");
+    compare_with_file(actual, manifest_dir, path, test_case_name, bless)
+}
+
+#[track_caller]
+fn compare_with_file(
+    actual: &str,
+    manifest_dir: &str,
+    path: &str,
+    test_case_name: &str,
+    bless: bool,
+) {
+    let full_path = Path::new(manifest_dir).join(path);
+
+    let expected = match std::fs::read_to_string(&full_path) {
+        Ok(expected) => Some(expected),
+        Err(e) if e.kind() == ErrorKind::NotFound && bless => None,
+        Err(e) if e.kind() == ErrorKind::NotFound => panic!(
+            "test case `{}` golden file `{}` does not exist\n  run with `{}=1` to create it",
+            test_case_name, path, BLESS_VAR
+        ),
+        Err(e) => panic!(
+            "test case `{}` could not read golden file `{}`: {}",
+            test_case_name, path, e
+        ),
+    };
+
+    if expected.as_deref() == Some(actual) {
+        return;
+    }
+
+    if bless {
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent).unwrap_or_else(|e| {
+                panic!("could not create directory `{}`: {}", parent.display(), e)
+            });
+        }
+        std::fs::write(&full_path, actual).unwrap_or_else(|e| {
+            panic!(
+                "could not write golden file `{}`: {}",
+                full_path.display(),
+                e
+            )
+        });
+        return;
+    }
+
+    panic!(
+        "test case `{}` result does not match golden file `{}`\n  run with `{}=1` to update it\n\nDiff < file / result > :\n{}",
+        test_case_name,
+        path,
+        BLESS_VAR,
+        super::diff::diff(expected.as_deref().unwrap_or_default(), actual)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::catch_unwind;
+
+    /// Fresh directory in system temp dir, unique for the test.
+    fn temp_dir(name: &str) -> String {
+        let dir =
+            std::env::temp_dir().join(format!("test-case-golden-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.to_str().unwrap().to_string()
+    }
+
+    fn panic_message(compare: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let payload = catch_unwind(compare).unwrap_err();
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn blesses_missing_and_different_files_then_compares_with_them() {
+        let dir = temp_dir("bless");
+
+        compare_with_file("first\n", &dir, "golden/case.txt", "case", true);
+        assert_eq!(
+            std::fs::read_to_string(Path::new(&dir).join("golden/case.txt")).unwrap(),
+            "first\n"
+        );
+        compare_with_file("first\n", &dir, "golden/case.txt", "case", false);
+
+        compare_with_file("second\n", &dir, "golden/case.txt", "case", true);
+        compare_with_file("second\n", &dir, "golden/case.txt", "case", false);
+    }
+
+    #[test]
+    fn reports_missing_file_without_bless() {
+        let dir = temp_dir("missing");
+
+        let message =
+            panic_message(|| compare_with_file("first", &dir, "missing.txt", "case", false));
+        assert_eq!(
+            message,
+            "test case `case` golden file `missing.txt` does not exist\n  run with `TEST_CASE_BLESS=1` to create it"
+        );
+        assert!(!Path::new(&dir).join("missing.txt").exists());
+    }
+
+    #[test]
+    fn reports_diff_of_different_file_without_bless() {
+        let dir = temp_dir("different");
+        std::fs::write(Path::new(&dir).join("case.txt"), "a\nb\n").unwrap();
+
+        let message =
+            panic_message(|| compare_with_file("a\nc\n", &dir, "case.txt", "case", false));
+        assert!(
+            message.starts_with(
+                "test case `case` result does not match golden file `case.txt`\n  run with `TEST_CASE_BLESS=1` to update it"
+            ),
+            "{message}"
+        );
+        assert!(message.contains(" a\n"), "{message}");
+        assert_eq!(
+            std::fs::read_to_string(Path::new(&dir).join("case.txt")).unwrap(),
+            "a\nb\n"
+        );
+    }
+}
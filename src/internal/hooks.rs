@@ -0,0 +1,64 @@
+//! Guards running `#[test_case_teardown(..)]` functions when test case ends, even if it panics.
+//!
+//! Guards are dropped during unwinding when test case fails, so panicking teardown aborts the
+//! process instead of failing the test.
+
+use std::ops::{Deref, DerefMut};
+
+/// Calls teardown function without arguments when dropped.
+pub struct Teardown<F: FnOnce()>(Option<F>);
+
+impl<F: FnOnce()> Teardown<F> {
+    pub fn new(teardown: F) -> Self {
+        Self(Some(teardown))
+    }
+}
+
+impl<F: FnOnce()> Drop for Teardown<F> {
+    fn drop(&mut self) {
+        if let Some(teardown) = self.0.take() {
+            teardown()
+        }
+    }
+}
+
+/// Holds value returned by setup function and passes it to teardown function when dropped.
+pub struct Fixture<T, F: FnOnce(T)> {
+    value: Option<T>,
+    teardown: Option<F>,
+}
+
+impl<T, F: FnOnce(T)> Fixture<T, F> {
+    pub fn new(value: T, teardown: F) -> Self {
+        Self {
+            value: Some(value),
+            teardown: Some(teardown),
+        }
+    }
+}
+
+impl<T, F: FnOnce(T)> Deref for Fixture<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+            .as_ref()
+            .expect("fixture is available until dropped")
+    }
+}
+
+impl<T, F: FnOnce(T)> DerefMut for Fixture<T, F> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+            .as_mut()
+            .expect("fixture is available until dropped")
+    }
+}
+
+impl<T, F: FnOnce(T)> Drop for Fixture<T, F> {
+    fn drop(&mut self) {
+        if let (Some(value), Some(teardown)) = (self.value.take(), self.teardown.take()) {
+            teardown(value)
+        }
+    }
+}
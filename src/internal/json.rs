@@ -0,0 +1,144 @@
+//! Structural comparison of JSON documents (`json_eq`, `json_subset`, requires `with-json` feature).
+//!
+//! Both sides are parsed, so key order and whitespace don't matter. The first difference is
+//! reported along with JSON pointer (RFC 6901) to the place where documents diverge.
+
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Documents have to be equal.
+    Eq,
+    /// Every object key and array element from expected document has to be present in the result,
+    /// result may contain more.
+    Subset,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    difference: Option<Difference>,
+}
+
+#[derive(Debug)]
+struct Difference {
+    pointer: String,
+    message: String,
+}
+
+impl Report {
+    pub fn new<A, E>(actual: &A, expected: &E, mode: Mode) -> Self
+    where
+        A: AsRef<str> + ?Sized,
+        E: AsRef<str> + ?Sized,
+    {
+        let parse = |json: &str, side: &str| {
+            serde_json::from_str::<Value>(json).map_err(|e| Difference {
+                pointer: String::new(),
+                message: format!("{side} is not valid JSON: {e}"),
+            })
+        };
+
+        let difference = match (
+            parse(actual.as_ref(), "result"),
+            parse(expected.as_ref(), "expected value"),
+        ) {
+            (Ok(actual), Ok(expected)) => first_difference(&actual, &expected, mode, String::new()),
+            (Err(difference), _) | (_, Err(difference)) => Some(difference),
+        };
+
+        Self { difference }
+    }
+
+    pub fn holds(&self) -> bool {
+        self.difference.is_none()
+    }
+}
+
+fn first_difference(
+    actual: &Value,
+    expected: &Value,
+    mode: Mode,
+    pointer: String,
+) -> Option<Difference> {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            for (key, expected) in expected {
+                let pointer = format!("{}/{}", pointer, escape(key));
+                match actual.get(key) {
+                    Some(actual) => {
+                        if let Some(difference) = first_difference(actual, expected, mode, pointer)
+                        {
+                            return Some(difference);
+                        }
+                    }
+                    None => {
+                        return Some(Difference {
+                            pointer,
+                            message: format!("missing key, expected {expected}"),
+                        })
+                    }
+                }
+            }
+            if mode == Mode::Eq {
+                if let Some((key, actual)) =
+                    actual.iter().find(|(key, _)| !expected.contains_key(*key))
+                {
+                    return Some(Difference {
+                        pointer: format!("{}/{}", pointer, escape(key)),
+                        message: format!("unexpected key, found {actual}"),
+                    });
+                }
+            }
+            None
+        }
+        (Value::Array(actual), Value::Array(expected)) => {
+            for (index, expected) in expected.iter().enumerate() {
+                let pointer = format!("{pointer}/{index}");
+                match actual.get(index) {
+                    Some(actual) => {
+                        if let Some(difference) = first_difference(actual, expected, mode, pointer)
+                        {
+                            return Some(difference);
+                        }
+                    }
+                    None => {
+                        return Some(Difference {
+                            pointer,
+                            message: format!("missing element, expected {expected}"),
+                        })
+                    }
+                }
+            }
+            if mode == Mode::Eq && actual.len() > expected.len() {
+                return Some(Difference {
+                    pointer: format!("{}/{}", pointer, expected.len()),
+                    message: format!("unexpected element, found {}", actual[expected.len()]),
+                });
+            }
+            None
+        }
+        (actual, expected) if actual == expected => None,
+        (actual, expected) => Some(Difference {
+            pointer,
+            message: format!("expected {expected}, found {actual}"),
+        }),
+    }
+}
+
+/// Escapes `~` and `/` in object key, so it can be used as JSON pointer reference token.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.difference {
+            None => f.write_str("JSON documents match"),
+            Some(Difference { pointer, message }) if pointer.is_empty() => {
+                write!(f, "at root: {message}")
+            }
+            Some(Difference { pointer, message }) => write!(f, "at `{pointer}`: {message}"),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Map assertions (`has_key`, `has_entry`).
+//!
+//! `HashMap` and `BTreeMap` are queried with their own `get`, so the expected key can be anything the
+//! key type borrows as, either directly (`String` key by `String` or `str`) or through a reference
+//! (`"port"` finds `String` key). Other maps, as well as keys comparable only with `PartialEq`, fall
+//! back to linear search over entries. The choice is made at the call site with autoref-based
+//! specialization: generated code has to bring [`IndexedLookup`], [`DerefIndexedLookup`] and
+//! [`LinearLookup`] into scope and call `(&&&Lookup::new(...)).get()`.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::ops::Deref;
+
+/// Map together with its entries (for linear search) and the key to look up.
+pub struct Lookup<'m, 'k, M, I, Q: ?Sized> {
+    map: &'m M,
+    entries: I,
+    key: &'k Q,
+}
+
+impl<'m, 'k, M, I, Q: ?Sized> Lookup<'m, 'k, M, I, Q> {
+    pub fn new(map: &'m M, entries: I, key: &'k Q) -> Self {
+        Self { map, entries, key }
+    }
+}
+
+pub trait IndexedLookup<'m, V> {
+    fn get(&self) -> Option<&'m V>;
+}
+
+impl<'m, 'k, K, V, S, I, Q> IndexedLookup<'m, V> for &&Lookup<'m, 'k, HashMap<K, V, S>, I, Q>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    fn get(&self) -> Option<&'m V> {
+        self.map.get(self.key)
+    }
+}
+
+impl<'m, 'k, K, V, I, Q> IndexedLookup<'m, V> for &&Lookup<'m, 'k, BTreeMap<K, V>, I, Q>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    fn get(&self) -> Option<&'m V> {
+        self.map.get(self.key)
+    }
+}
+
+pub trait DerefIndexedLookup<'m, V> {
+    fn get(&self) -> Option<&'m V>;
+}
+
+impl<'m, 'k, K, V, S, I, Q> DerefIndexedLookup<'m, V> for &Lookup<'m, 'k, HashMap<K, V, S>, I, Q>
+where
+    K: Borrow<Q::Target> + Hash + Eq,
+    Q: Deref,
+    Q::Target: Hash + Eq,
+    S: BuildHasher,
+{
+    fn get(&self) -> Option<&'m V> {
+        self.map.get(&**self.key)
+    }
+}
+
+impl<'m, 'k, K, V, I, Q> DerefIndexedLookup<'m, V> for &Lookup<'m, 'k, BTreeMap<K, V>, I, Q>
+where
+    K: Borrow<Q::Target> + Ord,
+    Q: Deref,
+    Q::Target: Ord,
+{
+    fn get(&self) -> Option<&'m V> {
+        self.map.get(&**self.key)
+    }
+}
+
+pub trait LinearLookup<'m, V> {
+    fn get(&self) -> Option<&'m V>;
+}
+
+impl<'m, 'k, M, I, K, V, Q> LinearLookup<'m, V> for Lookup<'m, 'k, M, I, Q>
+where
+    I: Iterator<Item = (&'m K, &'m V)> + Clone,
+    K: PartialEq<Q> + 'm,
+    V: 'm,
+    Q: ?Sized,
+{
+    fn get(&self) -> Option<&'m V> {
+        get(self.entries.clone(), self.key)
+    }
+}
+
+/// Value of the entry whose key equals `key`.
+pub fn get<'a, K, V, Q>(entries: impl IntoIterator<Item = (&'a K, &'a V)>, key: &Q) -> Option<&'a V>
+where
+    K: PartialEq<Q> + 'a,
+    V: 'a,
+    Q: ?Sized,
+{
+    entries
+        .into_iter()
+        .find(|(entry_key, _)| *entry_key == key)
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn finds_owned_key_by_borrowed_one() {
+        let map = HashMap::from([("port".to_string(), 80)]);
+        assert_eq!(get(map.iter(), &"port"), Some(&80));
+        assert_eq!(get(map.iter(), &"port".to_string()), Some(&80));
+        assert_eq!(get(map.iter(), &"host"), None);
+    }
+
+    #[test]
+    fn finds_key_of_the_same_type() {
+        let map = BTreeMap::from([(1, "one"), (2, "two")]);
+        assert_eq!(get(map.iter(), &2), Some(&"two"));
+        assert_eq!(get(map.iter(), &3), None);
+    }
+
+    struct Unhashable(u8);
+
+    impl PartialEq<u8> for Unhashable {
+        fn eq(&self, other: &u8) -> bool {
+            self.0 == *other
+        }
+    }
+
+    // Borrows pick the lookup strategy, clippy takes them for needless ones.
+    #[test]
+    #[allow(clippy::needless_borrow)]
+    fn looks_up_keys_with_map_get_when_possible() {
+        let map = HashMap::from([("port".to_string(), 80)]);
+        assert_eq!(
+            (&&&Lookup::new(&map, map.iter(), &"port".to_string())).get(),
+            Some(&80)
+        );
+        assert_eq!((&&&Lookup::new(&map, map.iter(), "port")).get(), Some(&80));
+        assert_eq!((&&&Lookup::new(&map, map.iter(), &"port")).get(), Some(&80));
+        assert_eq!((&&&Lookup::new(&map, map.iter(), &"host")).get(), None);
+
+        // `Box<str>` can't be compared with `str` at all, only borrowed as it.
+        let map: BTreeMap<Box<str>, u8> = BTreeMap::from([("port".into(), 80)]);
+        assert_eq!((&&&Lookup::new(&map, map.iter(), "port")).get(), Some(&80));
+        assert_eq!((&&&Lookup::new(&map, map.iter(), &"port")).get(), Some(&80));
+
+        let map = BTreeMap::from([(1u8, "one"), (2, "two")]);
+        assert_eq!((&&&Lookup::new(&map, map.iter(), &2)).get(), Some(&"two"));
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)]
+    fn falls_back_to_linear_search() {
+        let entries = [(Unhashable(1), "one"), (Unhashable(2), "two")];
+        let iter = entries.iter().map(|(key, value)| (key, value));
+        assert_eq!(
+            (&&&Lookup::new(&entries, iter.clone(), &2u8)).get(),
+            Some(&"two")
+        );
+        assert_eq!((&&&Lookup::new(&entries, iter, &3u8)).get(), None);
+    }
+}
@@ -0,0 +1,19 @@
+//! Runtime support for code generated by `test_case` macros.
+//!
+//! Items in this module are not part of public API and may change without notice.
+
+pub mod debug;
+pub mod diff;
+pub mod golden;
+pub mod hooks;
+#[cfg(feature = "with-json")]
+pub mod json;
+pub mod map;
+pub mod path;
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "smol"))]
+pub mod runtime;
+pub mod sequence;
+pub mod set_relation;
+
+#[cfg(feature = "insta")]
+pub use insta;
@@ -0,0 +1,317 @@
+//! Path checks (`existing_path`, `file`, `dir`, `symlink`, `readonly` and their sub-assertions).
+//!
+//! Every check which doesn't hold records what was found on disk, so that failure message can
+//! tell more than just `assertion failed`.
+
+use std::fmt::{Display, Formatter};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Any,
+    Dir,
+    File,
+    Symlink,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    path: PathBuf,
+    /// `false` when path doesn't point to expected kind of entry, further checks are skipped then.
+    found: bool,
+    failures: Vec<String>,
+}
+
+impl Report {
+    pub fn new<P: AsRef<Path> + ?Sized>(path: &P, kind: Kind) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let found = describe(&path);
+        let holds = match kind {
+            Kind::Any => path.exists(),
+            Kind::Dir => path.is_dir(),
+            Kind::File => path.is_file(),
+            Kind::Symlink => path
+                .symlink_metadata()
+                .map_or(false, |meta| meta.file_type().is_symlink()),
+        };
+
+        let mut failures = vec![];
+        if !holds {
+            failures.push(format!("expected {}, found {}", kind, found));
+        }
+
+        Self {
+            path,
+            found: holds,
+            failures,
+        }
+    }
+
+    pub fn with_content<C: AsRef<[u8]> + ?Sized>(mut self, expected: &C) -> Self {
+        if !self.found {
+            return self;
+        }
+
+        let expected = expected.as_ref();
+        match std::fs::read(&self.path) {
+            Ok(content) if content == expected => {}
+            Ok(content) => self.failures.push(format!(
+                "expected content {:?}, found {:?}",
+                String::from_utf8_lossy(expected),
+                String::from_utf8_lossy(&content)
+            )),
+            Err(e) => self.failures.push(format!("could not read content: {e}")),
+        }
+        self
+    }
+
+    /// `expected` can be of any integer type, values which are not valid file length (e.g. negative
+    /// ones) are reported as failure instead of being truncated.
+    pub fn with_len<L: TryInto<u64> + Display>(mut self, expected: L) -> Self {
+        if !self.found {
+            return self;
+        }
+
+        let description = expected.to_string();
+        let expected = match expected.try_into() {
+            Ok(expected) => expected,
+            Err(_) => {
+                self.failures.push(format!(
+                    "expected {description} bytes, which is not a valid length"
+                ));
+                return self;
+            }
+        };
+        match self.path.metadata().map(|meta| meta.len()) {
+            Ok(len) if len == expected => {}
+            Ok(len) => self
+                .failures
+                .push(format!("expected {expected} bytes, found {len} bytes")),
+            Err(e) => self.failures.push(format!("could not read metadata: {e}")),
+        }
+        self
+    }
+
+    pub fn containing<I>(mut self, expected: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<Path>,
+    {
+        if !self.found {
+            return self;
+        }
+
+        let missing: Vec<String> = expected
+            .into_iter()
+            .filter(|entry| !self.path.join(entry).exists())
+            .map(|entry| format!("{:?}", entry.as_ref()))
+            .collect();
+        if !missing.is_empty() {
+            self.failures.push(format!(
+                "missing entries [{}], found [{}]",
+                missing.join(", "),
+                entries(&self.path).join(", ")
+            ));
+        }
+        self
+    }
+
+    pub fn readonly(mut self) -> Self {
+        if !self.found {
+            return self;
+        }
+
+        match self.path.metadata() {
+            Ok(meta) if meta.permissions().readonly() => {}
+            Ok(_) => self
+                .failures
+                .push("expected readonly permissions, found writable".to_string()),
+            Err(e) => self.failures.push(format!("could not read metadata: {e}")),
+        }
+        self
+    }
+
+    pub fn holds(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+fn describe(path: &Path) -> String {
+    let kind = |meta: &Metadata| {
+        if meta.is_dir() {
+            "directory"
+        } else if meta.is_file() {
+            "file"
+        } else {
+            "special file"
+        }
+    };
+
+    match (path.symlink_metadata(), path.metadata()) {
+        (Err(_), _) => "nothing".to_string(),
+        (Ok(link), Ok(target)) if link.file_type().is_symlink() => {
+            format!("symlink to {}", kind(&target))
+        }
+        (Ok(link), Err(_)) if link.file_type().is_symlink() => "broken symlink".to_string(),
+        (Ok(meta), _) => kind(&meta).to_string(),
+    }
+}
+
+/// Sorted names of directory entries.
+fn entries(path: &Path) -> Vec<String> {
+    let mut entries: Vec<String> = std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| format!("{:?}", entry.file_name()))
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Any => f.write_str("existing path"),
+            Kind::Dir => f.write_str("directory"),
+            Kind::File => f.write_str("file"),
+            Kind::Symlink => f.write_str("symlink"),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.holds() {
+            write!(f, "path {:?} holds", self.path)
+        } else {
+            write!(f, "path {:?}: {}", self.path, self.failures.join("; "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh directory in system temp dir, unique for the test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("test-case-path-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn failures(report: &Report) -> String {
+        let path = format!("path {:?}: ", report.path);
+        report.to_string().replacen(&path, "", 1)
+    }
+
+    #[test]
+    fn reports_what_was_found_instead_of_expected_kind() {
+        let dir = temp_dir("kind");
+        std::fs::write(dir.join("file"), "content").unwrap();
+
+        let report = Report::new(&dir.join("missing"), Kind::Any);
+        assert_eq!(failures(&report), "expected existing path, found nothing");
+        let report = Report::new(&dir.join("file"), Kind::Dir);
+        assert_eq!(failures(&report), "expected directory, found file");
+        let report = Report::new(&dir, Kind::File);
+        assert_eq!(failures(&report), "expected file, found directory");
+        let report = Report::new(&dir, Kind::Symlink);
+        assert_eq!(failures(&report), "expected symlink, found directory");
+        assert!(Report::new(&dir, Kind::Dir).holds());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn describes_symlinks() {
+        let dir = temp_dir("symlink");
+        std::fs::write(dir.join("file"), "content").unwrap();
+        std::os::unix::fs::symlink(dir.join("file"), dir.join("link")).unwrap();
+        std::os::unix::fs::symlink(dir.join("missing"), dir.join("broken")).unwrap();
+
+        assert!(Report::new(&dir.join("link"), Kind::Symlink).holds());
+        let report = Report::new(&dir.join("link"), Kind::Dir);
+        assert_eq!(
+            failures(&report),
+            "expected directory, found symlink to file"
+        );
+        let report = Report::new(&dir.join("broken"), Kind::File);
+        assert_eq!(failures(&report), "expected file, found broken symlink");
+    }
+
+    #[test]
+    fn reports_content_and_len_found_on_disk() {
+        let dir = temp_dir("content");
+        let file = dir.join("file");
+        std::fs::write(&file, "content").unwrap();
+
+        assert!(Report::new(&file, Kind::File)
+            .with_content("content")
+            .with_len(7)
+            .holds());
+        let report = Report::new(&file, Kind::File)
+            .with_content("other")
+            .with_len(3u8);
+        assert_eq!(
+            failures(&report),
+            r#"expected content "other", found "content"; expected 3 bytes, found 7 bytes"#
+        );
+    }
+
+    #[test]
+    fn reports_invalid_len_instead_of_truncating_it() {
+        let dir = temp_dir("len");
+        let file = dir.join("file");
+        std::fs::write(&file, "content").unwrap();
+
+        let report = Report::new(&file, Kind::File).with_len(-1);
+        assert_eq!(
+            failures(&report),
+            "expected -1 bytes, which is not a valid length"
+        );
+    }
+
+    #[test]
+    fn reports_missing_entries_along_with_found_ones() {
+        let dir = temp_dir("entries");
+        std::fs::write(dir.join("b"), "").unwrap();
+        std::fs::write(dir.join("a"), "").unwrap();
+
+        assert!(Report::new(&dir, Kind::Dir).containing(["a", "b"]).holds());
+        let report = Report::new(&dir, Kind::Dir).containing(["a", "c", "d"]);
+        assert_eq!(
+            failures(&report),
+            r#"missing entries ["c", "d"], found ["a", "b"]"#
+        );
+    }
+
+    #[test]
+    fn skips_further_checks_when_kind_does_not_match() {
+        let dir = temp_dir("skip");
+
+        let report = Report::new(&dir.join("missing"), Kind::File)
+            .with_content("content")
+            .with_len(7)
+            .readonly();
+        assert_eq!(failures(&report), "expected file, found nothing");
+    }
+
+    #[test]
+    fn reports_writable_permissions() {
+        let dir = temp_dir("readonly");
+        let file = dir.join("file");
+        std::fs::write(&file, "").unwrap();
+
+        let report = Report::new(&file, Kind::File).readonly();
+        assert_eq!(
+            failures(&report),
+            "expected readonly permissions, found writable"
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! Async runtimes driving test cases declared with `runtime = ...` option.
+
+use std::future::Future;
+
+#[cfg(feature = "tokio")]
+pub fn tokio<F: Future>(future: F) -> F::Output {
+    ::tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build tokio runtime")
+        .block_on(future)
+}
+
+#[cfg(feature = "async-std")]
+pub fn async_std<F: Future>(future: F) -> F::Output {
+    ::async_std::task::block_on(future)
+}
+
+#[cfg(feature = "smol")]
+pub fn smol<F: Future>(future: F) -> F::Output {
+    ::smol::block_on(future)
+}
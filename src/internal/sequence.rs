@@ -0,0 +1,238 @@
+//! Sequence checks (`contains_in_order`, `contains_subsequence`).
+//!
+//! Text results are searched with `str` methods and every other result is treated as an iterable.
+//! The choice is made at the call site with autoref-based specialization: generated code has to
+//! bring [`StrSequence`], [`IterSequence`] and [`DerefSequence`] into scope and call
+//! `(&&haystack).sequence_report(..)`.
+
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+
+pub struct Haystack<'a, T: ?Sized>(pub &'a T);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// Elements have to appear next to each other.
+    Contiguous,
+    /// Elements have to appear in the same order, possibly with other elements in between.
+    WithGaps,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    kind: Kind,
+    holds: bool,
+    haystack: String,
+    needle: String,
+    matched: usize,
+    needle_len: usize,
+}
+
+pub trait StrSequence<N: ?Sized> {
+    fn sequence_report(&self, needle: &N, kind: Kind) -> Report;
+}
+
+impl<'a, T, N> StrSequence<N> for &Haystack<'a, T>
+where
+    T: AsRef<str> + ?Sized,
+    N: AsRef<str> + ?Sized,
+{
+    fn sequence_report(&self, needle: &N, kind: Kind) -> Report {
+        let haystack = self.0.as_ref();
+        let needle = needle.as_ref();
+        let (holds, matched) = match kind {
+            Kind::Contiguous => (haystack.contains(needle), 0),
+            Kind::WithGaps => {
+                let haystack: Vec<char> = haystack.chars().collect();
+                let needle: Vec<char> = needle.chars().collect();
+                let matched = matched_with_gaps(&haystack, &needle);
+                (matched == needle.len(), matched)
+            }
+        };
+
+        Report {
+            kind,
+            holds,
+            haystack: format!("{haystack:?}"),
+            needle: format!("{needle:?}"),
+            matched,
+            needle_len: needle.chars().count(),
+        }
+    }
+}
+
+pub trait IterSequence<N: ?Sized> {
+    fn sequence_report(&self, needle: &N, kind: Kind) -> Report;
+}
+
+impl<'a, T, N, I> IterSequence<N> for &&Haystack<'a, T>
+where
+    T: ?Sized,
+    N: ?Sized,
+    &'a T: IntoIterator<Item = &'a I>,
+    for<'n> &'n N: IntoIterator<Item = &'n I>,
+    I: PartialEq + Debug + 'a,
+{
+    fn sequence_report(&self, needle: &N, kind: Kind) -> Report {
+        items_report(self.0.into_iter().collect(), needle, kind)
+    }
+}
+
+pub trait DerefSequence<N: ?Sized> {
+    fn sequence_report(&self, needle: &N, kind: Kind) -> Report;
+}
+
+impl<'a, T, U, N, I> DerefSequence<N> for Haystack<'a, T>
+where
+    T: Deref<Target = U> + ?Sized,
+    U: ?Sized + 'a,
+    N: ?Sized,
+    for<'h> &'h U: IntoIterator<Item = &'h I>,
+    for<'n> &'n N: IntoIterator<Item = &'n I>,
+    I: PartialEq + Debug,
+{
+    fn sequence_report(&self, needle: &N, kind: Kind) -> Report {
+        items_report(self.0.deref().into_iter().collect(), needle, kind)
+    }
+}
+
+fn items_report<'n, I, N>(haystack: Vec<&I>, needle: &'n N, kind: Kind) -> Report
+where
+    I: PartialEq + Debug + 'n,
+    N: ?Sized,
+    &'n N: IntoIterator<Item = &'n I>,
+{
+    let needle: Vec<&I> = needle.into_iter().collect();
+    let (holds, matched) = match kind {
+        Kind::Contiguous => (
+            needle.is_empty()
+                || haystack
+                    .windows(needle.len())
+                    .any(|w| w == needle.as_slice()),
+            0,
+        ),
+        Kind::WithGaps => {
+            let matched = matched_with_gaps(&haystack, &needle);
+            (matched == needle.len(), matched)
+        }
+    };
+
+    Report {
+        kind,
+        holds,
+        haystack: format!("{haystack:?}"),
+        needle: format!("{needle:?}"),
+        matched,
+        needle_len: needle.len(),
+    }
+}
+
+/// Number of leading `needle` elements found in `haystack` in order.
+fn matched_with_gaps<I: PartialEq>(haystack: &[I], needle: &[I]) -> usize {
+    let mut matched = 0;
+    for item in haystack {
+        if matched == needle.len() {
+            break;
+        }
+        if *item == needle[matched] {
+            matched += 1;
+        }
+    }
+    matched
+}
+
+impl Report {
+    pub fn holds(&self) -> bool {
+        self.holds
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.kind, self.holds) {
+            (Kind::Contiguous, true) => write!(
+                f,
+                "{} contains {} contiguously",
+                self.haystack, self.needle
+            ),
+            (Kind::Contiguous, false) => write!(
+                f,
+                "{} does not contain {} contiguously",
+                self.haystack, self.needle
+            ),
+            (Kind::WithGaps, true) => write!(
+                f,
+                "{} contains subsequence {}",
+                self.haystack, self.needle
+            ),
+            (Kind::WithGaps, false) => write!(
+                f,
+                "{} does not contain subsequence {}, only first {} of {} elements were found in order",
+                self.haystack, self.needle, self.matched, self.needle_len
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report<T, N>(haystack: &T, needle: &N, kind: Kind) -> String
+    where
+        T: ?Sized,
+        N: ?Sized,
+        for<'a> &'a T: IntoIterator<Item = &'a i32>,
+        for<'a> &'a N: IntoIterator<Item = &'a i32>,
+    {
+        (&&Haystack(haystack))
+            .sequence_report(needle, kind)
+            .to_string()
+    }
+
+    #[test]
+    fn reports_contiguous_elements() {
+        assert_eq!(
+            report(&[1, 2, 3], &[2, 3], Kind::Contiguous),
+            "[1, 2, 3] contains [2, 3] contiguously"
+        );
+        assert_eq!(
+            report(&[1, 2, 3], &[1, 3], Kind::Contiguous),
+            "[1, 2, 3] does not contain [1, 3] contiguously"
+        );
+    }
+
+    #[test]
+    fn reports_how_much_of_subsequence_was_found() {
+        assert_eq!(
+            report(&vec![1, 2, 3], &[1, 3], Kind::WithGaps),
+            "[1, 2, 3] contains subsequence [1, 3]"
+        );
+        assert_eq!(
+            report(&vec![1, 2, 3], &[1, 3, 2], Kind::WithGaps),
+            "[1, 2, 3] does not contain subsequence [1, 3, 2], only first 2 of 3 elements were found in order"
+        );
+    }
+
+    #[test]
+    fn reports_text_sequences() {
+        let report = |haystack: &str, needle: &str, kind| {
+            (&Haystack(haystack))
+                .sequence_report(needle, kind)
+                .to_string()
+        };
+
+        assert_eq!(
+            report("hello world", "lo w", Kind::Contiguous),
+            r#""hello world" contains "lo w" contiguously"#
+        );
+        assert_eq!(
+            report("hello world", "low", Kind::Contiguous),
+            r#""hello world" does not contain "low" contiguously"#
+        );
+        assert_eq!(
+            report("hello", "hlx", Kind::WithGaps),
+            r#""hello" does not contain subsequence "hlx", only first 2 of 3 elements were found in order"#
+        );
+    }
+}
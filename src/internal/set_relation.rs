@@ -0,0 +1,301 @@
+//! Set relations (`subset_of`, `superset_of`, `contains_all`, `contains_any`).
+//!
+//! Membership is checked with a `HashSet` when elements are `Hash + Eq` and falls back to linear
+//! `PartialEq` search otherwise. The choice is made at the call site with autoref-based
+//! specialization: generated code has to bring both [`HashedMembership`] and [`LinearMembership`]
+//! into scope and call `(&collection).membership()`.
+
+use std::collections::HashSet;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+
+pub struct Collection<'a, T>(Vec<&'a T>);
+
+pub struct Membership<'a, T>(Box<dyn Fn(&T) -> bool + 'a>);
+
+impl<'a, T> Collection<'a, T> {
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        Self(items.into_iter().collect())
+    }
+
+    /// Builds another collection with the same element type, so that the element type of
+    /// `expected` value is known when membership strategy is picked.
+    pub fn with_items<'b, I>(&self, items: I) -> Collection<'b, T>
+    where
+        I: IntoIterator<Item = &'b T>,
+    {
+        Collection::new(items)
+    }
+}
+
+impl<'a, T> Membership<'a, T> {
+    fn contains(&self, item: &T) -> bool {
+        (self.0)(item)
+    }
+}
+
+pub trait HashedMembership<'a, T> {
+    fn membership(&self) -> Membership<'a, T>;
+}
+
+impl<'a, T: Hash + Eq> HashedMembership<'a, T> for Collection<'a, T> {
+    fn membership(&self) -> Membership<'a, T> {
+        let items: HashSet<&'a T> = self.0.iter().copied().collect();
+        Membership(Box::new(move |item| items.contains(item)))
+    }
+}
+
+pub trait LinearMembership<'a, T> {
+    fn membership(&self) -> Membership<'a, T>;
+}
+
+impl<'a, T: PartialEq> LinearMembership<'a, T> for &Collection<'a, T> {
+    fn membership(&self) -> Membership<'a, T> {
+        let items = self.0.clone();
+        Membership(Box::new(move |item| items.contains(&item)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    SubsetOf,
+    SupersetOf,
+    ContainsAll,
+    ContainsAny,
+    SameElements,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    relation: Relation,
+    holds: bool,
+    missing: Vec<String>,
+    unexpected: Vec<String>,
+}
+
+impl Report {
+    /// Checks `relation` between `actual` (result of the test) and `expected` (value given in the
+    /// attribute).
+    ///
+    /// `actual_membership` and `expected_membership` have to be built out of respective collections.
+    pub fn new<T: Debug>(
+        relation: Relation,
+        actual: &Collection<'_, T>,
+        actual_membership: &Membership<'_, T>,
+        expected: &Collection<'_, T>,
+        expected_membership: &Membership<'_, T>,
+    ) -> Self {
+        let not_in = |items: &Collection<'_, T>, membership: &Membership<'_, T>| {
+            items
+                .0
+                .iter()
+                .filter(|item| !membership.contains(item))
+                .map(|item| format!("{item:?}"))
+                .collect::<Vec<_>>()
+        };
+
+        Self::from_differences(
+            relation,
+            expected.0.len(),
+            not_in(expected, actual_membership),
+            not_in(actual, expected_membership),
+        )
+    }
+
+    /// Checks `relation` comparing elements with `PartialEq` between their types, so that e.g.
+    /// `String` keys of a map can be compared with `&str` literals.
+    pub fn by_partial_eq<A, E>(
+        relation: Relation,
+        actual: impl IntoIterator<Item = A>,
+        expected: impl IntoIterator<Item = E>,
+    ) -> Self
+    where
+        A: PartialEq<E> + Debug,
+        E: Debug,
+    {
+        let actual: Vec<A> = actual.into_iter().collect();
+        let expected: Vec<E> = expected.into_iter().collect();
+        let missing = expected
+            .iter()
+            .filter(|e| !actual.iter().any(|a| a == *e))
+            .map(|e| format!("{e:?}"))
+            .collect();
+        let unexpected = actual
+            .iter()
+            .filter(|a| !expected.iter().any(|e| *a == e))
+            .map(|a| format!("{a:?}"))
+            .collect();
+
+        Self::from_differences(relation, expected.len(), missing, unexpected)
+    }
+
+    fn from_differences(
+        relation: Relation,
+        expected_len: usize,
+        missing: Vec<String>,
+        unexpected: Vec<String>,
+    ) -> Self {
+        let holds = match relation {
+            Relation::SubsetOf => unexpected.is_empty(),
+            Relation::SupersetOf | Relation::ContainsAll => missing.is_empty(),
+            Relation::ContainsAny => missing.len() < expected_len,
+            Relation::SameElements => missing.is_empty() && unexpected.is_empty(),
+        };
+
+        Self {
+            relation,
+            holds,
+            missing,
+            unexpected,
+        }
+    }
+
+    pub fn holds(&self) -> bool {
+        self.holds
+    }
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Relation::SubsetOf => f.write_str("subset_of"),
+            Relation::SupersetOf => f.write_str("superset_of"),
+            Relation::ContainsAll => f.write_str("contains_all"),
+            Relation::ContainsAny => f.write_str("contains_any"),
+            Relation::SameElements => f.write_str("same_elements"),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.holds {
+            return write!(f, "`{}` holds", self.relation);
+        }
+
+        write!(f, "`{}` does not hold", self.relation)?;
+        match self.relation {
+            Relation::SubsetOf => {
+                write!(f, ", unexpected elements: [{}]", self.unexpected.join(", "))
+            }
+            Relation::SupersetOf | Relation::ContainsAll => {
+                write!(f, ", missing elements: [{}]", self.missing.join(", "))
+            }
+            Relation::ContainsAny => {
+                write!(
+                    f,
+                    ", none of the elements found: [{}]",
+                    self.missing.join(", ")
+                )
+            }
+            Relation::SameElements => write!(
+                f,
+                ", missing elements: [{}], unexpected elements: [{}]",
+                self.missing.join(", "),
+                self.unexpected.join(", ")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<T: Hash + Eq + Debug>(relation: Relation, actual: &[T], expected: &[T]) -> Report {
+        let actual = Collection::new(actual);
+        let expected = actual.with_items(expected);
+        Report::new(
+            relation,
+            &actual,
+            &actual.membership(),
+            &expected,
+            &expected.membership(),
+        )
+    }
+
+    #[test]
+    fn reports_relation_which_holds() {
+        let report = check(Relation::SubsetOf, &[1, 2], &[1, 2, 3]);
+        assert!(report.holds());
+        assert_eq!(report.to_string(), "`subset_of` holds");
+    }
+
+    #[test]
+    fn reports_unexpected_elements_of_subset() {
+        let report = check(Relation::SubsetOf, &[1, 4, 5], &[1, 2, 3]);
+        assert!(!report.holds());
+        assert_eq!(
+            report.to_string(),
+            "`subset_of` does not hold, unexpected elements: [4, 5]"
+        );
+    }
+
+    #[test]
+    fn reports_missing_elements_of_superset() {
+        let report = check(Relation::SupersetOf, &["a", "b"], &["a", "c"]);
+        assert_eq!(
+            report.to_string(),
+            r#"`superset_of` does not hold, missing elements: ["c"]"#
+        );
+
+        let report = check(Relation::ContainsAll, &["a", "b"], &["c", "d"]);
+        assert_eq!(
+            report.to_string(),
+            r#"`contains_all` does not hold, missing elements: ["c", "d"]"#
+        );
+    }
+
+    #[test]
+    fn reports_contains_any_only_when_nothing_is_found() {
+        assert!(check(Relation::ContainsAny, &[1, 2], &[2, 3]).holds());
+        assert_eq!(
+            check(Relation::ContainsAny, &[1, 2], &[3, 4]).to_string(),
+            "`contains_any` does not hold, none of the elements found: [3, 4]"
+        );
+    }
+
+    #[test]
+    fn reports_both_missing_and_unexpected_elements() {
+        assert!(check(Relation::SameElements, &[2, 1], &[1, 2]).holds());
+        assert_eq!(
+            check(Relation::SameElements, &[1, 2], &[2, 3]).to_string(),
+            "`same_elements` does not hold, missing elements: [3], unexpected elements: [1]"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_linear_search_for_elements_without_hash() {
+        let actual = [1.0, 2.0];
+        let expected = [2.0, 3.0];
+        let actual = Collection::new(&actual);
+        let expected = actual.with_items(&expected);
+        let report = Report::new(
+            Relation::SubsetOf,
+            &actual,
+            &(&actual).membership(),
+            &expected,
+            &(&expected).membership(),
+        );
+        assert_eq!(
+            report.to_string(),
+            "`subset_of` does not hold, unexpected elements: [1.0]"
+        );
+    }
+
+    #[test]
+    fn compares_elements_of_different_types() {
+        let keys = ["port".to_string(), "timeout".to_string()];
+        let report = Report::by_partial_eq(Relation::SameElements, &keys, ["timeout", "port"]);
+        assert!(report.holds());
+
+        let report = Report::by_partial_eq(Relation::SameElements, &keys, ["port", "host"]);
+        assert_eq!(
+            report.to_string(),
+            r#"`same_elements` does not hold, missing elements: ["host"], unexpected elements: ["timeout"]"#
+        );
+    }
+}
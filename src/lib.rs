@@ -90,9 +90,21 @@
 //! # Documentation
 //!
 //! Most up to date documentation is available in our [wiki](https://github.com/frondeus/test-case/wiki).
+pub use test_case_macros::apply_suite;
+pub use test_case_macros::case_set;
 pub use test_case_macros::test_case;
 pub use test_case_macros::test_case as case;
+pub use test_case_macros::test_case_impl;
+pub use test_case_macros::test_case_set;
+pub use test_case_macros::test_case_suite;
 pub use test_case_macros::test_matrix;
 
+mod matcher;
+
+pub use matcher::Matcher;
+
+#[doc(hidden)]
+pub mod internal;
+
 #[cfg(feature = "with-regex")]
 pub use regex::*;
@@ -0,0 +1,35 @@
+/// User defined check usable in complex test-cases via `satisfies` keyword.
+///
+/// Matchers compose with `not`, `and` and `or` like any built-in keyword and their description
+/// is included in failure message.
+///
+/// ```rust
+/// use test_case::{test_case, Matcher};
+///
+/// struct Even;
+///
+/// impl Matcher<u32> for Even {
+///     fn matches(&self, actual: &u32) -> bool {
+///         actual % 2 == 0
+///     }
+///
+///     fn describe(&self) -> String {
+///         "an even number".to_string()
+///     }
+/// }
+///
+/// #[test_case(1 => it satisfies Even)]
+/// #[test_case(3 => it satisfies Even and gt 5)]
+/// #[test_case(0 => it satisfies Even and not gt 0)]
+/// fn double(x: u32) -> u32 {
+///     x * 2
+/// }
+/// ```
+pub trait Matcher<T: ?Sized> {
+    /// Returns `true` if `actual` value passes the check.
+    fn matches(&self, actual: &T) -> bool;
+
+    /// Describes value expected by this matcher, e.g. `"a valid IBAN"`. Failure message reads
+    /// `expected <description>`.
+    fn describe(&self) -> String;
+}
@@ -0,0 +1,32 @@
+#![cfg(test)]
+use test_case::{test_case, test_matrix};
+
+#[test_case(1, 2)]
+fn missing_argument(_a: u32, _b: u32, _c: u32) {
+    unreachable!("Should never compile")
+}
+
+#[test_case(1, 2, 3)]
+#[test_case(1, 2, 3, 4)]
+fn extra_arguments(_a: u32) {
+    unreachable!("Should never compile")
+}
+
+fn db() -> u32 {
+    0
+}
+
+#[test_case(1)]
+fn fixtures_are_not_counted(_a: u32, #[fixture] _db: u32, _b: u32) {
+    unreachable!("Should never compile")
+}
+
+#[test_matrix([1, 2], [3, 4])]
+fn missing_axis(_a: u32, _b: u32, _c: u32) {
+    unreachable!("Should never compile")
+}
+
+#[test_matrix([1, 2], [3, 4], [5, 6])]
+fn extra_axis(_a: u32, _b: u32) {
+    unreachable!("Should never compile")
+}
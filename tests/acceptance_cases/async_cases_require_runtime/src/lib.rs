@@ -0,0 +1,28 @@
+#![cfg(test)]
+
+use test_case::test_case;
+
+#[test_case(1 => 1)]
+async fn missing_runtime(x: u32) -> u32 {
+    x
+}
+
+#[test_case(1 => 1 ; runtime = tokio)]
+async fn missing_runtime_feature(x: u32) -> u32 {
+    x
+}
+
+#[test_case(1 => 1)]
+#[my_runtime::test]
+async fn unsupported_runtime_attribute(x: u32) -> u32 {
+    x
+}
+
+async fn is_one(x: u32) {
+    assert_eq!(x, 1)
+}
+
+#[test_case(1 => using async is_one)]
+fn async_assertion_on_sync_fn(x: u32) -> u32 {
+    x
+}
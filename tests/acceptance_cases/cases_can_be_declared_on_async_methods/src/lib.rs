@@ -12,3 +12,21 @@ async fn works_seamlessly_with_tokio(arg: i32) -> usize {
 async fn works_seamlessly_with_async_std(arg: i32) -> usize {
         arg as usize
     }
+
+async fn is_hundred(value: usize) {
+    assert_eq!(value, 100)
+}
+
+#[test_case(100i32 => using async is_hundred)]
+#[test_case(100i32 => with async |v: usize| assert_eq!(async { v }.await, 100))]
+#[tokio::test]
+async fn supports_async_assertions_with_tokio(arg: i32) -> usize {
+    arg as usize
+}
+
+#[test_case(100i32 => using async is_hundred)]
+#[test_case(100i32 => with async |v: usize| is_hundred(v).await)]
+#[async_std::test]
+async fn supports_async_assertions_with_async_std(arg: i32) -> usize {
+    arg as usize
+}
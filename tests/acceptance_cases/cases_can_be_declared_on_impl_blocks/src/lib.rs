@@ -0,0 +1,82 @@
+use test_case::test_case_impl;
+
+pub struct HTTPCounter(u32);
+
+#[test_case_impl]
+impl HTTPCounter {
+    #[test_case(1 => 1)]
+    #[test_case(5 => 5)]
+    pub fn new(start: u32) -> u32 {
+        HTTPCounter(start).0
+    }
+
+    #[test_case(2 => 3 ; receiver = HTTPCounter(1))]
+    #[test_case(3 => 13 ; "from ten" ; receiver = HTTPCounter(10))]
+    pub fn add(&self, n: u32) -> u32 {
+        self.0 + n
+    }
+
+    #[test_case(2 => 3 ; receiver = HTTPCounter(1))]
+    pub fn increment(&mut self, n: u32) -> u32 {
+        self.0 += n;
+        self.0
+    }
+
+    #[test_case(1 => 8 ; receiver = HTTPCounter(7))]
+    pub fn into_sum(self, n: u32) -> u32 {
+        self.0 + n
+    }
+
+    #[test_case::test_matrix([1, 2], [3, 4] ; receiver = HTTPCounter(0))]
+    pub fn sum(&self, a: u32, b: u32) {
+        assert!(self.0 + a + b < 7)
+    }
+
+    #[test_case(1 => panics "too small")]
+    pub fn check(n: u32) {
+        if n < 2 {
+            panic!("too small")
+        }
+    }
+
+    /// Scales counter up.
+    #[inline]
+    #[cfg(not(feature = "scale_down"))]
+    #[test_case(2 => 4)]
+    pub fn scale(n: u32) -> u32 {
+        n * 2
+    }
+
+    /// Scales counter down.
+    #[inline]
+    #[cfg(feature = "scale_down")]
+    #[test_case(4 => 2)]
+    pub fn scale(n: u32) -> u32 {
+        n / 2
+    }
+
+    pub fn untouched(&self) -> u32 {
+        self.0
+    }
+}
+
+#[test_case_impl]
+impl HTTPCounter {
+    #[test_case(4 => 4)]
+    pub fn get(start: u32) -> u32 {
+        HTTPCounter(start).0
+    }
+}
+
+#[test_case_impl]
+impl HTTPCounter {
+    pub fn reset(&mut self) {
+        self.0 = 0
+    }
+}
+
+#[cfg(test)]
+#[test_case::test_case(1 => 1)]
+fn http_counter(start: u32) -> u32 {
+    HTTPCounter(start).0
+}
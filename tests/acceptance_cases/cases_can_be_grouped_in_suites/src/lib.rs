@@ -0,0 +1,83 @@
+use std::collections::{BTreeMap, HashMap};
+use test_case::{apply_suite, test_case_suite};
+
+pub trait Storage {
+    fn put(&mut self, key: &str, value: &str);
+    fn get(&self, key: &str) -> Option<String>;
+    fn len(&self) -> usize;
+}
+
+#[derive(Default)]
+pub struct MemStorage(HashMap<String, String>);
+
+#[derive(Default)]
+pub struct SortedStorage(BTreeMap<String, String>);
+
+#[derive(Default)]
+pub struct ForgetfulStorage;
+
+impl Storage for MemStorage {
+    fn put(&mut self, key: &str, value: &str) {
+        self.0.insert(key.to_string(), value.to_string());
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Storage for SortedStorage {
+    fn put(&mut self, key: &str, value: &str) {
+        self.0.insert(key.to_string(), value.to_string());
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Storage for ForgetfulStorage {
+    fn put(&mut self, _key: &str, _value: &str) {}
+
+    fn get(&self, _key: &str) -> Option<String> {
+        None
+    }
+
+    fn len(&self) -> usize {
+        0
+    }
+}
+
+#[test_case_suite(S: Storage + Default)]
+mod storage_suite {
+    fn filled(entries: &[(&str, &str)]) -> S {
+        let mut storage = S::default();
+        for (key, value) in entries {
+            storage.put(key, value);
+        }
+        storage
+    }
+
+    #[test_case("a" => Some("1".to_string()))]
+    #[test_case("missing" => None)]
+    fn gets_stored_value(key: &str) -> Option<String> {
+        filled(&[("a", "1"), ("b", "2")]).get(key)
+    }
+
+    #[test_case(&[("a", "1"), ("a", "2")] => 1 ; "overwritten key")]
+    #[test_case(&[("a", "1"), ("b", "2")] => 2 ; "distinct keys")]
+    fn counts_keys(entries: &[(&str, &str)]) -> usize {
+        filled(entries).len()
+    }
+}
+
+#[apply_suite(storage_suite, MemStorage, SortedStorage, ForgetfulStorage)]
+mod storage_suite {}
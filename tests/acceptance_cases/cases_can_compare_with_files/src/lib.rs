@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use std::fmt::{Display, Formatter};
+    use test_case::{test_case, test_matrix};
+
+    struct Greeting(&'static str);
+
+    impl Display for Greeting {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            writeln!(f, "Hello,")?;
+            writeln!(f, "{}!", self.0)
+        }
+    }
+
+    #[test_case("Hello,\nWorld!\n" => equals_file "golden/world.txt" ; "str result")]
+    #[test_case("Hello,\nRust!\n" => equals_file "golden/world.txt" ; "str result mismatch")]
+    fn text(text: &'static str) -> &'static str {
+        text
+    }
+
+    #[test_case("World" => equals_file "golden/world.txt" ; "display result")]
+    #[test_case("Rust" => equals_file "golden/{case}.txt" ; "rust")]
+    fn greeting(name: &'static str) -> Greeting {
+        Greeting(name)
+    }
+
+    #[test_case(2 => equals_file "golden/missing.txt" ; "missing file")]
+    fn number(n: u32) -> String {
+        n.to_string()
+    }
+
+    #[test_matrix(["World", "Rust"] => equals_file "golden/matrix_{case}.txt")]
+    fn matrix(name: &'static str) -> String {
+        Greeting(name).to_string()
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(test)]
+use std::fmt::Debug;
+use std::mem::size_of;
+use test_case::{test_case, test_matrix};
+
+#[test_case(1, 2 => "3" ; types(u8, u16, u32, u64))]
+#[test_case(7, 0 => "7" ; "adding zero" ; types(i16, i64))]
+fn adds_for_every_width<T: From<u8> + std::ops::Add<Output = T> + Debug>(a: u8, b: u8) -> String {
+    format!("{:?}", T::from(a) + T::from(b))
+}
+
+#[test_case(0 ; types(u8, u16))]
+fn fails_for_too_narrow_types<T: TryFrom<u32>>(_: u8) {
+    assert!(T::try_from(300).is_ok(), "{} bytes are not enough", size_of::<T>())
+}
+
+#[test_case(0u8 => 0 ; consts(N = [1, 4, 16]))]
+#[test_case(7u8 => 7 * 16 ; consts(N = [16]))]
+fn sums_array<const N: usize>(value: u8) -> usize {
+    [value as usize; N].iter().sum()
+}
+
+#[test_case(1 ; types(u32, u64) ; consts(N = [2, 3]))]
+fn combines_types_and_consts<T: From<u8> + Copy + Default + PartialEq + Debug, const N: usize>(
+    value: u8,
+) {
+    let array = [T::from(value); N];
+    assert_eq!(array.len(), N);
+    assert_ne!(array[0], T::default());
+}
+
+#[test_case("ab" => 2 ; consts(N = [1, 2]))]
+fn infers_types_when_only_consts_are_listed<T: AsRef<str>, const N: usize>(value: T) -> usize {
+    value.as_ref().len() * N / N
+}
+
+#[test_matrix([1, 2], [3] ; types(u16, i32))]
+fn matrix_for_every_type<T: From<u8> + PartialOrd>(a: u8, b: u8) {
+    assert!(T::from(a) < T::from(b))
+}
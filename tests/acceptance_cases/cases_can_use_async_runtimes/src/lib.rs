@@ -0,0 +1,33 @@
+#![cfg(test)]
+
+use test_case::{test_case, test_matrix};
+
+async fn double(x: u32) -> u32 {
+    async { x * 2 }.await
+}
+
+#[test_case(1 => 2 ; runtime = tokio)]
+#[test_case(2 => 4 ; "on async std" ; runtime = async_std)]
+#[test_case(3 => 6 ; runtime = smol)]
+#[test_case(4 => 9 ; "failing" ; runtime = tokio)]
+#[test_case(5 => panics "unsupported" ; runtime = smol)]
+async fn doubles(x: u32) -> u32 {
+    if x == 5 {
+        panic!("unsupported")
+    }
+    double(x).await
+}
+
+#[test_case(1 ; runtime = async_std)]
+async fn returns_result(x: u32) -> Result<(), String> {
+    if double(x).await == 2 {
+        Ok(())
+    } else {
+        Err("not doubled".to_string())
+    }
+}
+
+#[test_matrix([1, 2] => is gt 0 ; runtime = tokio)]
+async fn matrix(x: u32) -> u32 {
+    double(x).await
+}
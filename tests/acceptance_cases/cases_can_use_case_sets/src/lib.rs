@@ -0,0 +1,52 @@
+#![cfg(test)]
+use test_case::{case_set, test_case, test_case_set, test_matrix};
+
+case_set!(tricky_inputs: ["", "\0", "ünïcödé" ; "unicode"]);
+
+case_set! {
+    sums: [(1, 2 => 3), (-1, 1 => 0 ; "opposite"), (i32::MAX, 1 => panics "overflow")];
+    ignored_sums: [(2, 2 => ignore 5 ; "wrong")];
+    pairs: [((1, 2)) => 3, ((2, 2)) => 4 ; "same"]
+}
+
+fn encode(input: &str) -> Vec<u8> {
+    input.bytes().rev().collect()
+}
+
+fn decode(input: &[u8]) -> String {
+    String::from_utf8(input.iter().rev().copied().collect()).unwrap()
+}
+
+#[test_case_set(tricky_inputs)]
+fn roundtrips(input: &str) {
+    assert_eq!(decode(&encode(input)), input)
+}
+
+#[test_case("plain")]
+#[test_case_set(tricky_inputs)]
+fn encodes_to_same_length(input: &str) {
+    assert_eq!(encode(input).len(), input.len())
+}
+
+#[test_case_set(tricky_inputs)]
+#[test_case("plain" ; "set above")]
+fn decodes_to_valid_utf8(input: &str) {
+    assert!(std::str::from_utf8(input.as_bytes()).is_ok())
+}
+
+#[test_case_set(sums)]
+#[test_case_set(ignored_sums)]
+fn adds(a: i32, b: i32) -> i32 {
+    a.checked_add(b).expect("overflow")
+}
+
+#[test_case_set(pairs)]
+fn adds_pair((a, b): (i32, i32)) -> i32 {
+    a + b
+}
+
+#[test_matrix(["x", "y"])]
+#[test_case::test_case_set(tricky_inputs)]
+fn concatenates(input: &str) {
+    assert!(format!("{input}{input}").len() >= input.len())
+}
@@ -0,0 +1,26 @@
+//! Dummy test harness: `#[harness::test]` marks function with `#[test]` and prefixes its name with
+//! `harness_`, so that it's visible in test output which attribute was used.
+
+extern crate proc_macro;
+
+use proc_macro::{Ident, TokenStream, TokenTree};
+
+#[proc_macro_attribute]
+pub fn test(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut output: TokenStream = "#[test]".parse().unwrap();
+    let mut after_fn = false;
+
+    output.extend(input.into_iter().map(|token| match token {
+        TokenTree::Ident(ident) if after_fn => {
+            after_fn = false;
+            TokenTree::Ident(Ident::new(&format!("harness_{ident}"), ident.span()))
+        }
+        TokenTree::Ident(ident) => {
+            after_fn = ident.to_string() == "fn";
+            TokenTree::Ident(ident)
+        }
+        token => token,
+    }));
+
+    output
+}
@@ -0,0 +1,29 @@
+#![cfg(test)]
+
+mod per_case {
+    use test_case::test_case;
+
+    #[test_case(1 => 1 ; test_attr = harness::test)]
+    #[test_case(2 => 2 ; "named" ; test_attr = harness::test)]
+    #[test_case(3 => 3)]
+    fn identity(x: u32) -> u32 {
+        x
+    }
+}
+
+mod per_function {
+    use test_case::{test_case, test_matrix};
+
+    #[test_case(1 => 1)]
+    #[test_case(2 => 2 ; "default overridden" ; test_attr = test)]
+    #[test_case_attr(harness::test)]
+    fn identity(x: u32) -> u32 {
+        x
+    }
+
+    #[test_matrix([1, 2] => is gt 0)]
+    #[test_case_attr(harness::test)]
+    fn matrix(x: u32) -> u32 {
+        x
+    }
+}
@@ -0,0 +1,37 @@
+#![cfg(test)]
+
+use test_case::test_case;
+
+#[test_case(r#"{"b": [1, 2], "a": "x"}"# => is json_eq r#"{ "a": "x", "b": [1, 2] }"#)]
+#[test_case(r#"{"a": {"b": null}}"# => is json_eq r#"{"a": {"b": null}}"# ; "nested objects")]
+#[test_case(r#"{"a": 1, "b": 2}"# => is json_subset r#"{"b": 2}"#)]
+#[test_case(r#"{"a": [{"b": 1, "c": 2}, 3]}"# => is json_subset r#"{"a": [{"c": 2}]}"# ; "nested subset")]
+#[test_case(r#"{"a": 1, "b": 2}"# => is not json_eq r#"{"b": 2}"#)]
+#[test_case(r#"{"a": 1}"# => is json_eq r#"{"a": 1}"# and contains_in_order "a" ; "combined")]
+fn json(text: &str) -> &str {
+    text
+}
+
+#[test_case(r#"{"users": [{"name": "a"}, {"name": "b"}]}"# => is json_eq r#"{"users": [{"name": "a"}, {"name": "c"}]}"#)]
+#[should_panic(expected = r#"at `/users/1/name`: expected "c", found "b""#)]
+fn different_value(text: &str) -> &str {
+    text
+}
+
+#[test_case(r#"{"a": 1, "b/c": 2}"# => is json_eq r#"{"a": 1}"#)]
+#[should_panic(expected = "at `/b~1c`: unexpected key, found 2")]
+fn unexpected_key(text: &str) -> &str {
+    text
+}
+
+#[test_case(r#"{"a": [1]}"# => is json_subset r#"{"a": [1, 2]}"#)]
+#[should_panic(expected = "at `/a/1`: missing element, expected 2")]
+fn missing_element(text: &str) -> &str {
+    text
+}
+
+#[test_case("{" => is json_eq "{}")]
+#[should_panic(expected = "at root: result is not valid JSON")]
+fn invalid_json(text: &str) -> &str {
+    text
+}
@@ -0,0 +1,50 @@
+#![cfg(test)]
+
+use test_case::{test_case, test_matrix};
+
+#[derive(Debug, PartialEq)]
+struct User {
+    name: &'static str,
+    age: u8,
+    admin: bool,
+}
+
+#[test_case("alice", 30 => User { name: "alice", age: 30, admin: false })]
+#[test_case("bob", 41 => User { name: "bob", age: 42, admin: false } ; "wrong age")]
+fn user(name: &'static str, age: u8) -> User {
+    User {
+        name,
+        age,
+        admin: false,
+    }
+}
+
+#[test_case(2, 2 => 4)]
+#[test_case(2, 3 => 4)]
+fn sum(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[test_case("abc" => "abc")]
+#[test_case("abc" => "abd".to_string())]
+fn text(v: &str) -> String {
+    v.to_string()
+}
+
+#[test_case("carol", 7 => User { name: "carol", age: 8, admin: false })]
+#[should_panic(
+    expected = "assertion `left == right` failed in test case `_carol_7_expects_user_name_carol_age_8_admin_false_`"
+)]
+fn failure_message_names_the_case(name: &'static str, age: u8) -> User {
+    User {
+        name,
+        age,
+        admin: false,
+    }
+}
+
+#[test_matrix([1, 2] => 2)]
+#[should_panic(expected = "Diff < left / right > :")]
+fn failure_message_in_matrix(v: u8) -> u8 {
+    v * 2 + 1
+}
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use test_case::{test_case, test_matrix};
+
+    #[derive(Debug, Serialize)]
+    struct User {
+        name: String,
+        roles: Vec<&'static str>,
+    }
+
+    fn user(name: &str, admin: bool) -> User {
+        let mut roles = vec!["reader"];
+        if admin {
+            roles.push("admin");
+        }
+        User {
+            name: name.to_string(),
+            roles,
+        }
+    }
+
+    #[test_case("alice", true => snapshot ; "admin by default format")]
+    #[test_case("bob", false => snapshot debug ; "reader as debug")]
+    #[test_case("carol", true => snapshot json ; "admin as json")]
+    #[test_case("dave", false => snapshot yaml ; "reader as yaml")]
+    fn snapshots(name: &str, admin: bool) -> User {
+        user(name, admin)
+    }
+
+    #[test_matrix(["erin", "frank"], [true, false] => snapshot yaml)]
+    fn matrix(name: &str, admin: bool) -> User {
+        user(name, admin)
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(test)]
+
+use test_case::test_case;
+
+#[test_case(7.0 => is gt 0.0 and lt 5.0)]
+#[should_panic(expected = "assertion `gt 0.0 and lt 5.0` failed\n  - `lt 5.0` failed\nresult: 7.0")]
+fn reports_failed_and_operand(v: f32) -> f32 {
+    v
+}
+
+#[test_case(vec![1, 2, 3] => it contains 6 or contains 7)]
+#[should_panic(expected = "  - `contains 6` failed\n  - `contains 7` failed\nresult: [1, 2, 3]")]
+fn reports_every_failed_or_operand(v: Vec<u8>) -> Vec<u8> {
+    v
+}
+
+#[test_case(2.0 => is (gt 0.0 or lt -1.5) and lt 2.0)]
+#[should_panic(expected = "assertion `(gt 0.0 or lt - 1.5) and lt 2.0` failed")]
+fn keeps_grouping_in_description(v: f32) -> f32 {
+    v
+}
+
+#[test_case(vec![1, 2, 3] => it len 3 and contains_all [3, 4])]
+#[should_panic(
+    expected = "  - `contains_all [3, 4]` failed: `contains_all` does not hold, missing elements: [4]"
+)]
+fn includes_details_of_failed_operand(v: Vec<u8>) -> Vec<u8> {
+    v
+}
+
+pub struct NoDebug(Vec<u8>);
+
+impl NoDebug {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[test_case(vec![1, 2] => is len 3)]
+#[should_panic(expected = "does not implement Debug>")]
+fn handles_results_without_debug(v: Vec<u8>) -> NoDebug {
+    NoDebug(v)
+}
+
+#[test_case(vec![1, 2] => is count 3)]
+#[should_panic(expected = "assertion `count 3` failed\n  - `count 3` failed")]
+fn skips_result_consumed_by_assertion(v: Vec<u8>) -> impl IntoIterator<Item = u8> {
+    v.into_iter()
+}
+
+#[test_case(2.0 => is not ((gt 0.0 or lt -1.5) and lt 2.0))]
+fn respects_grouping_in_negation(v: f32) -> f32 {
+    v
+}
@@ -0,0 +1,57 @@
+#![cfg(test)]
+
+use test_case::test_case;
+
+#[test_case(1 => "one")]
+fn simple_expected_value(x: u8) -> u8 {
+    x
+}
+
+#[test_case(1 => is greater_than "zero")]
+fn complex_expected_value(x: u8) -> u8 {
+    x
+}
+
+#[test_case(vec![1, 2] => is len "two")]
+fn complex_expected_len(v: Vec<u8>) -> Vec<u8> {
+    v
+}
+
+#[test_case(vec![1, 2] => it contains "one")]
+fn complex_expected_element(v: Vec<u8>) -> Vec<u8> {
+    v
+}
+
+#[test_case("one" => 1)]
+fn argument_of_wrong_type(x: u8) -> u8 {
+    x
+}
+
+#[test_case(1.0 => is almost 1 precision 0.1)]
+fn complex_almost_equal(x: f64) -> f64 {
+    x
+}
+
+#[test_case(1 => it has_key "one")]
+fn complex_expected_key(x: u8) -> std::collections::HashMap<u8, u8> {
+    [(x, x)].into()
+}
+
+#[test_case(1 => it satisfies 1)]
+fn complex_matcher(x: u8) -> u8 {
+    x
+}
+
+#[test_case(1 => with |v: &str| assert_eq!(v, "one"))]
+fn closure_of_wrong_type(x: u8) -> u8 {
+    x
+}
+
+#[test_case(1 => using check_str)]
+fn function_of_wrong_type(x: u8) -> u8 {
+    x
+}
+
+fn check_str(v: &str) {
+    assert_eq!(v, "one")
+}
@@ -58,7 +58,8 @@ fn in_parens(_: f32) -> f32 {
 #[test_case(1.0 => is gt 0.0 or lt 0.0)]
 #[test_case(-2.0 => is gt 0.0 or lt 0.0)]
 #[test_case(-2.0 => is (gt 0.0 or lt 0.0) and lt -1.0)]
-#[test_case(1.0 => is (gt 0.0 or lt -1.5) and lt 2.0)]
+#[test_case(1.0 => is (gt 0.0 or lt -1.5) and lt 3.0)]
+#[test_case(1.0 => is not ((gt 0.0 or lt -1.5) and lt 2.0))]
 #[test_case(0.3 => is (gt 0.0 and lt 1.0) or gt 1.2)]
 #[test_case(0.7 => is (gt 0.0 and lt 1.0) or gt 1.2)]
 fn combinators(v: f32) -> f32 {
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use test_case::{test_case, test_matrix};
+
+struct TempDb(HashMap<&'static str, usize>);
+
+fn db() -> TempDb {
+    TempDb(HashMap::from([("one", 1), ("two", 2)]))
+}
+
+fn offset() -> usize {
+    10
+}
+
+#[test_case(1 => 1)]
+#[test_case(3 => 2)]
+fn fixture_after_case_arguments(n: usize, #[fixture] db: TempDb) -> usize {
+    db.0.values().filter(|v| **v <= n).count()
+}
+
+#[test_case("one", 1 => 11)]
+#[test_case("two", 2 => 12)]
+fn fixtures_between_case_arguments(
+    #[fixture] db: TempDb,
+    key: &str,
+    #[fixture] offset: usize,
+    expected: usize,
+) -> usize {
+    assert_eq!(db.0[key], expected);
+    expected + offset
+}
+
+#[test_case(1)]
+#[test_case(2)]
+fn fixture_is_constructed_per_case(n: usize, #[fixture] mut db: TempDb) {
+    assert_eq!(db.0.len(), 2);
+    db.0.insert("three", n);
+    assert_eq!(db.0.len(), 3);
+}
+
+#[test_matrix(["one", "two"], [1, 2])]
+fn fixture_in_matrix(key: &str, n: usize, #[fixture] db: TempDb) {
+    assert!(db.0[key] * n <= 4)
+}
+
+#[test_case("one" => 21)]
+#[test_case_setup(offset)]
+fn fixture_with_setup(key: &str, #[test_case::fixture] db: TempDb, offset: usize) -> usize {
+    db.0[key] + offset + 10
+}
@@ -0,0 +1,33 @@
+#![cfg(test)]
+use test_case::{test_case, test_matrix};
+
+#[test_case(width = 10, height = 2 => 20)]
+#[test_case(height = 3, width = 4 => 12 ; "swapped order")]
+#[test_case(5, height = 5 => 25 ; "positional first")]
+fn area(width: u32, height: u32) -> u32 {
+    width * height
+}
+
+#[test_case(width = 10 => 10)]
+#[test_case(width = 2, depth = 3 => 6)]
+#[test_case(1, 2, 3 => 6 ; "all positional")]
+#[test_case_defaults(height = 1, depth = 1)]
+fn volume(width: u32, height: u32, depth: u32) -> u32 {
+    width * height * depth
+}
+
+fn unit() -> u32 {
+    1
+}
+
+#[test_case(scale = 3 => 3)]
+#[test_case(scale = 3, unit = 2 => 6 ; "fixture given by name")]
+fn scaled(scale: u32, #[fixture] unit: u32) -> u32 {
+    scale * unit
+}
+
+#[test_matrix([1, 2])]
+#[test_case_defaults(offset = 10)]
+fn matrix_with_defaults(value: u32, offset: u32) {
+    assert!(value + offset > 10)
+}
@@ -0,0 +1,53 @@
+#![cfg(test)]
+
+use std::collections::{BTreeMap, HashMap};
+use test_case::test_case;
+
+fn config(entries: &[(&'static str, u32)]) -> HashMap<&'static str, u32> {
+    entries.iter().copied().collect()
+}
+
+#[test_case(&[("port", 80)] => it has_key "port")]
+#[test_case(&[("port", 80)] => it has_key "host")]
+#[test_case(&[("port", 80)] => it has_entry "port" => 80)]
+#[test_case(&[("port", 80)] => it has_entry "port" => 443)]
+#[test_case(&[("port", 80)] => it has_entry "host" => 80)]
+#[test_case(&[("port", 80), ("timeout", 5)] => it keys eq ["timeout", "port"])]
+#[test_case(&[("port", 80), ("timeout", 5)] => it keys eq ["port", "host"])]
+#[test_case(&[("port", 80)] => it has_key "port" and not has_key "host")]
+#[test_case(&[("port", 80)] => it has_entry "port" => 443 or has_entry "port" => 80)]
+fn hash_map(entries: &[(&'static str, u32)]) -> HashMap<&'static str, u32> {
+    config(entries)
+}
+
+#[test_case(&[(1, "one"), (2, "two")] => it has_key 1 and has_entry 2 => "two")]
+#[test_case(&[(1, "one"), (2, "two")] => it keys eq [1, 2])]
+#[test_case(&[(1, "one")] => it not has_entry 1 => "two")]
+fn btree_map(entries: &[(u8, &'static str)]) -> BTreeMap<u8, &'static str> {
+    entries.iter().copied().collect()
+}
+
+#[test_case("port" => it has_key "port")]
+#[test_case("port" => it has_key "port".to_string() and has_entry "port" => ())]
+#[test_case("port" => it keys eq ["port"])]
+fn owned_keys(key: &str) -> HashMap<String, ()> {
+    HashMap::from([(key.to_string(), ())])
+}
+
+#[test_case("port" => it has_key "host")]
+#[should_panic(expected = r#"key "host" not found, available keys: ["port"]"#)]
+fn missing_key(key: &str) -> HashMap<String, ()> {
+    owned_keys(key)
+}
+
+#[test_case("port" => it has_entry "port" => 443)]
+#[should_panic(expected = r#"key "port" maps to 80, expected 443"#)]
+fn different_value(key: &str) -> BTreeMap<String, u32> {
+    BTreeMap::from([(key.to_string(), 80)])
+}
+
+// `Box<str>` keys can only be borrowed as `str`, not compared with it.
+#[test_case("port" => it has_key "port" and has_entry "port" => 80)]
+fn borrowed_keys(key: &str) -> HashMap<Box<str>, u32> {
+    HashMap::from([(key.into(), 80)])
+}
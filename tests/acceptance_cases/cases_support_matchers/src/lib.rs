@@ -0,0 +1,46 @@
+#![cfg(test)]
+
+use test_case::{test_case, Matcher};
+
+struct ValidIban;
+
+impl Matcher<String> for ValidIban {
+    fn matches(&self, actual: &String) -> bool {
+        actual.len() >= 15 && actual.chars().take(2).all(|c| c.is_ascii_uppercase())
+    }
+
+    fn describe(&self) -> String {
+        "a valid IBAN".to_string()
+    }
+}
+
+struct CountryCode(&'static str);
+
+impl Matcher<String> for CountryCode {
+    fn matches(&self, actual: &String) -> bool {
+        actual.starts_with(self.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("an IBAN from {}", self.0)
+    }
+}
+
+fn valid_iban() -> ValidIban {
+    ValidIban
+}
+
+#[test_case("DE89370400440532013000" => it satisfies valid_iban())]
+#[test_case("DE89370400440532013000" => it satisfies valid_iban() and not (satisfies CountryCode("PL")) ; "combined with not")]
+#[test_case("PL61109010140000071219812874" => it satisfies CountryCode("DE") or satisfies CountryCode("PL") ; "combined with or")]
+#[test_case("DE89370400440532013000" => it satisfies valid_iban() and len 22 ; "combined with builtin")]
+#[test_case("12" => it satisfies valid_iban() ; "invalid")]
+fn iban(text: &str) -> String {
+    text.to_string()
+}
+
+#[test_case("DE89370400440532013000" => it satisfies CountryCode("PL"))]
+#[should_panic(expected = "  - `satisfies CountryCode(\"PL\")` failed: expected an IBAN from PL")]
+fn wrong_country(text: &str) -> String {
+    text.to_string()
+}
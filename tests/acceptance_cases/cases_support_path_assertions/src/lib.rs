@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use test_case::test_case;
+
+    fn path(val: &str) -> PathBuf {
+        PathBuf::from(val)
+    }
+
+    /// Creates `name` in temporary directory, which is writable unless `readonly` is set.
+    fn temp_file(name: &str, readonly: bool) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "test_case_path_assertions_{}_{}",
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_file(&path);
+        std::fs::write(&path, "temp").unwrap();
+        let mut permissions = std::fs::metadata(&path).unwrap().permissions();
+        permissions.set_readonly(readonly);
+        std::fs::set_permissions(&path, permissions).unwrap();
+        path
+    }
+
+    #[test_case("fixtures/hello.txt" => is file with_content "hello")]
+    #[test_case("fixtures/hello.txt" => is file with_len 5)]
+    #[test_case("fixtures/hello.txt" => is file with_content "hello" with_len 5)]
+    #[test_case("fixtures/dir" => is dir containing ["a.txt", "b.txt"])]
+    #[test_case("fixtures/hello.txt" => is not symlink)]
+    #[test_case("fixtures/hello.txt" => is file with_len 5 and not dir ; "combined")]
+    fn fixture(val: &str) -> PathBuf {
+        path(val)
+    }
+
+    #[test_case("readonly", true => is readonly)]
+    #[test_case("writable", false => is file and not readonly)]
+    fn permissions(name: &str, readonly: bool) -> PathBuf {
+        temp_file(name, readonly)
+    }
+
+    #[test_case("link" => is symlink)]
+    #[test_case("followed_link" => is file with_content "temp")]
+    fn link(name: &str) -> PathBuf {
+        let target = temp_file(name, false);
+        let link = target.with_extension("link");
+        let _ = std::fs::remove_file(&link);
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(&target, &link).unwrap();
+        link
+    }
+
+    #[test_case("fixtures/hello.txt" => is file with_content "bye")]
+    #[should_panic(expected = r#"expected content "bye", found "hello""#)]
+    fn wrong_content(val: &str) -> PathBuf {
+        path(val)
+    }
+
+    #[test_case("fixtures/hello.txt" => is file with_len 42)]
+    #[should_panic(expected = "expected 42 bytes, found 5 bytes")]
+    fn wrong_len(val: &str) -> PathBuf {
+        path(val)
+    }
+
+    #[test_case("fixtures/hello.txt" => is file with_len -1)]
+    #[should_panic(expected = "expected -1 bytes, which is not a valid length")]
+    fn invalid_len(val: &str) -> PathBuf {
+        path(val)
+    }
+
+    #[test_case("fixtures/dir" => is dir containing ["a.txt", "c.txt"])]
+    #[should_panic(expected = r#"missing entries ["c.txt"], found ["a.txt", "b.txt"]"#)]
+    fn missing_entry(val: &str) -> PathBuf {
+        path(val)
+    }
+
+    #[test_case("fixtures/hello.txt" => is dir containing ["a.txt"])]
+    #[should_panic(expected = r#"path "fixtures/hello.txt": expected directory, found file"#)]
+    fn wrong_kind(val: &str) -> PathBuf {
+        path(val)
+    }
+
+    #[test_case("fixtures/missing.txt" => is file)]
+    #[should_panic(expected = "expected file, found nothing")]
+    fn missing_file(val: &str) -> PathBuf {
+        path(val)
+    }
+
+    #[test_case("not_readonly", false => is readonly)]
+    #[should_panic(expected = "expected readonly permissions, found writable")]
+    fn not_readonly(name: &str, readonly: bool) -> PathBuf {
+        temp_file(name, readonly)
+    }
+}
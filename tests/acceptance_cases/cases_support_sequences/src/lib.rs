@@ -0,0 +1,41 @@
+#![cfg(test)]
+
+use std::collections::VecDeque;
+use test_case::test_case;
+
+#[test_case(vec![1, 2, 3, 4] => it contains_in_order [2, 3])]
+#[test_case(vec![1, 2, 3, 4] => it contains_in_order [2, 4])]
+#[test_case(vec![1] => it contains_in_order [1, 2, 3])]
+#[test_case(vec![] => it contains_in_order [1])]
+#[test_case(vec![] => it contains_in_order [])]
+#[test_case(vec![1, 2, 3, 4] => it contains_subsequence [1, 3, 4])]
+#[test_case(vec![1, 2, 3, 4] => it contains_subsequence [1, 4, 3])]
+#[test_case(vec![1] => it contains_subsequence [1, 2, 3])]
+#[test_case(vec![1, 2, 3, 4] => it contains_subsequence [2, 4] and not contains_in_order [2, 4])]
+fn vec(items: Vec<u32>) -> Vec<u32> {
+    items
+}
+
+#[test_case(&[1, 2, 3] => it contains_in_order [2, 3])]
+#[test_case(&[] => it not contains_subsequence [2, 3])]
+fn slice(items: &[u8]) -> &[u8] {
+    items
+}
+
+#[test_case(&[1, 2, 3] => it contains_in_order vec![1, 2])]
+fn deque(items: &[u8]) -> VecDeque<u8> {
+    items.iter().copied().collect()
+}
+
+#[test_case("hello world" => it contains_in_order "o w")]
+#[test_case("hello world" => it contains_in_order "hw")]
+#[test_case("hello world" => it contains_subsequence "hw")]
+#[test_case("hi" => it contains_subsequence "hello")]
+fn str(text: &str) -> &str {
+    text
+}
+
+#[test_case("hello world" => it contains_in_order "world".to_string())]
+fn string(text: &str) -> String {
+    text.to_string()
+}
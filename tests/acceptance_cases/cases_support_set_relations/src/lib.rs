@@ -0,0 +1,34 @@
+#![cfg(test)]
+
+use std::collections::{BTreeSet, HashSet};
+use test_case::test_case;
+
+#[test_case(vec![1, 2] => it subset_of [1, 2, 3])]
+#[test_case(vec![1, 2, 4, 5] => it subset_of [1, 2, 3])]
+#[test_case(vec![1, 2, 3] => it superset_of [1, 3])]
+#[test_case(vec![1, 2, 3] => it superset_of [1, 4, 5])]
+#[test_case(vec![1, 2, 3] => it contains_all [3, 2, 1])]
+#[test_case(vec![1, 2, 3] => it contains_all [3, 7])]
+#[test_case(vec![1, 2, 3] => it contains_any [7, 3])]
+#[test_case(vec![1, 2, 3] => it contains_any [7, 8])]
+fn hashable(items: Vec<u32>) -> Vec<u32> {
+    items
+}
+
+#[test_case(vec![1.0, 2.5] => it subset_of [1.0, 2.5, 3.0])]
+#[test_case(vec![1.0, 2.5] => it contains_all [0.5])]
+#[test_case(vec![1.0, 2.5] => it not contains_any [0.5, 4.0])]
+fn partial_eq_only(items: Vec<f64>) -> Vec<f64> {
+    items
+}
+
+#[test_case(&["read", "write"] => it contains_all ["read", "write"] and contains_any ["write", "admin"])]
+#[test_case(&["read"] => it subset_of ["read", "write"] and not contains_any ["admin"])]
+fn permissions(items: &[&'static str]) -> HashSet<&'static str> {
+    items.iter().copied().collect()
+}
+
+#[test_case(vec!['a', 'b'] => it superset_of BTreeSet::from(['a']))]
+fn other_collections(items: Vec<char>) -> BTreeSet<char> {
+    items.into_iter().collect()
+}
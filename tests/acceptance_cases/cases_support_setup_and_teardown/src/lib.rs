@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::PathBuf;
+use test_case::{test_case, test_matrix};
+
+fn answer() -> u32 {
+    42
+}
+
+#[test_case(1 => 43)]
+#[test_case(2 => 44)]
+#[test_case_setup(answer)]
+fn setup_value_is_passed_as_last_argument(x: u32, answer: u32) -> u32 {
+    x + answer
+}
+
+#[test_matrix([1, 2], [10, 20])]
+#[test_case_setup(answer)]
+fn setup_value_is_passed_to_matrix_cases(x: u32, y: u32, answer: u32) {
+    assert!(x + y < answer)
+}
+
+#[test_case(1)]
+#[test_case(2)]
+#[test_case_setup(Vec::new)]
+#[test_case_teardown(|values: Vec<u32>| assert_eq!(values.len(), 1))]
+fn fixture_is_borrowed_mutably_when_teardown_is_declared(x: u32, values: &mut Vec<u32>) {
+    values.push(x)
+}
+
+fn create_file() -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "test_case_setup_and_teardown_{:?}",
+        std::thread::current().id()
+    ));
+    fs::write(&path, "fixture").unwrap();
+    path
+}
+
+fn remove_file(path: PathBuf) {
+    fs::remove_file(path).unwrap()
+}
+
+#[test_case("fixture" => true)]
+#[test_case("other" => false)]
+#[test_case_setup(create_file)]
+#[test_case_teardown(remove_file)]
+fn fixture_is_borrowed_when_teardown_is_declared(content: &str, path: &PathBuf) -> bool {
+    fs::read_to_string(path).unwrap() == content
+}
+
+#[test_case(1 => panics "case panicked")]
+#[test_case_setup(create_file)]
+#[test_case_teardown(remove_file)]
+fn teardown_receives_fixture_when_case_panics(_x: u32, path: &PathBuf) {
+    assert!(path.exists());
+    panic!("case panicked")
+}
+
+// Deliberately not `Clone`, teardown receives the very value test case used.
+pub struct Connection {
+    queries: Vec<&'static str>,
+}
+
+fn connect() -> Connection {
+    Connection { queries: vec![] }
+}
+
+fn disconnect(connection: Connection) {
+    assert_eq!(connection.queries, ["SELECT 1"])
+}
+
+#[test_case("SELECT 1")]
+#[test_case_setup(connect)]
+#[test_case_teardown(disconnect)]
+fn teardown_receives_fixture_used_by_case(query: &'static str, connection: &mut Connection) {
+    connection.queries.push(query)
+}
+
+fn report_teardown() {
+    println!("test teardown ran after the case")
+}
+
+#[test_case(1 ; "passing")]
+#[test_case(2 ; "failing")]
+#[test_case_teardown(report_teardown)]
+fn teardown_runs_even_if_case_fails(x: u32) {
+    assert_eq!(x, 1)
+}
@@ -6,3 +6,8 @@ use test_case::test_case;
 fn fail_on_missing_with_regex_feature(_: u8) -> String {
     todo!()
 }
+
+#[test_case(2 => is json_eq "{}")]
+fn fail_on_missing_with_json_feature(_: u8) -> String {
+    todo!()
+}
@@ -0,0 +1,34 @@
+#![cfg(test)]
+use test_case::{test_case, test_case_impl};
+
+pub struct Counter(u32);
+
+impl Counter {
+    #[test_case(1)]
+    fn method_outside_of_test_case_impl(&self, _n: u32) {
+        unreachable!("Should never compile")
+    }
+}
+
+#[test_case_impl]
+impl Counter {
+    #[test_case(1)]
+    fn method_without_receiver_option(&self, _n: u32) {
+        unreachable!("Should never compile")
+    }
+
+    #[test_case(1 ; receiver = Counter(1))]
+    fn associated_function_with_receiver_option(_n: u32) {
+        unreachable!("Should never compile")
+    }
+}
+
+pub struct Wrapper<T>(T);
+
+#[test_case_impl]
+impl<T> Wrapper<T> {
+    #[test_case(1)]
+    fn generic_impl(_n: u32) {
+        unreachable!("Should never compile")
+    }
+}
@@ -0,0 +1,25 @@
+#![cfg(test)]
+
+use test_case::{test_case, test_matrix};
+
+#[test_case(1 2 => matches ; "two arguments" ; test_atr = my_test)]
+fn every_part_is_malformed(_: u8) -> Option<u8> {
+    None
+}
+
+#[test_case(1 => 2 3 ; "trailing token")]
+fn trailing_token_after_expression(x: u8) -> u8 {
+    x
+}
+
+#[test_matrix([1, "a"], 1..)]
+fn every_axis_is_malformed(_: u8, _: u8) {}
+
+#[test_case(1 => 1 ; "valid")]
+#[test_case(2 => ; "missing expected value")]
+#[test_case(3 4 => 3)]
+#[test_matrix([5, "five"])]
+#[test_case(6 => "six" ; "valid but expecting wrong type")]
+fn every_attribute_is_reported(x: u8) -> u8 {
+    x
+}
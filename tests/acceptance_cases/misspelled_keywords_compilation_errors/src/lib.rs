@@ -0,0 +1,28 @@
+#![cfg(test)]
+
+use test_case::test_case;
+
+#[test_case(vec![1, 2, 3] => is lenght 3)]
+fn misspelled_complex_keyword(v: Vec<u8>) -> Vec<u8> {
+    v
+}
+
+#[test_case(5 => ignored 5)]
+fn misspelled_modifier(x: u8) -> u8 {
+    x
+}
+
+#[test_case(5 => panic "reason")]
+fn misspelled_result_keyword(_: u8) {
+    panic!("reason")
+}
+
+#[test_case("abc" => is matches_regexp "a.c")]
+fn keyword_requiring_disabled_feature(s: &str) -> &str {
+    s
+}
+
+#[test_case(5 => ignore[42] 5)]
+fn malformed_modifier(x: u8) -> u8 {
+    x
+}
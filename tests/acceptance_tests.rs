@@ -8,7 +8,7 @@ use std::path::PathBuf;
 use std::process::Command;
 
 macro_rules! run_acceptance_test {
-    ($cmd:expr, $case_name:expr) => {
+    (@run $cmd:expr, $case_name:expr, $locations:expr) => {
         with_settings!({snapshot_path => get_snapshot_directory()}, {
             let subcommand = Command::new("cargo")
                 .current_dir(PathBuf::from("tests").join("acceptance_cases").join($case_name))
@@ -20,13 +20,19 @@ macro_rules! run_acceptance_test {
             output.push_str(String::from_utf8_lossy(&subcommand.stdout).as_ref());
             output.push_str(String::from_utf8_lossy(&subcommand.stderr).as_ref());
 
-            let output = sanitize_lines(output);
+            let output = sanitize_lines(output, $locations);
 
-            insta::assert_display_snapshot!(output);
+            insta::assert_snapshot!(output);
         })
     };
+    (with_locations, $case_name:expr) => {
+        run_acceptance_test!(@run "test", $case_name, true)
+    };
+    ($cmd:expr, $case_name:expr) => {
+        run_acceptance_test!(@run $cmd, $case_name, false)
+    };
     ($case_name:expr) => {
-        run_acceptance_test!("test", $case_name)
+        run_acceptance_test!(@run "test", $case_name, false)
     }
 }
 
@@ -38,8 +44,17 @@ fn get_snapshot_directory() -> String {
         .to_string()
 }
 
-fn sanitize_lines(s: String) -> String {
+/// With `locations`, every error is followed by location of the code it points at, e.g.
+/// `error[E0308]: mismatched types --> src/lib.rs:5:18`.
+fn sanitize_lines(s: String, locations: bool) -> String {
     let re_time = Regex::new(r"\d+\.\d{2}s").expect("Building regex");
+    let re_thread_id = Regex::new(r"' \(\d+\) panicked").expect("Building regex");
+
+    let s = if locations {
+        with_error_locations(&s)
+    } else {
+        s
+    };
 
     let mut s = s
         .lines()
@@ -53,6 +68,7 @@ fn sanitize_lines(s: String) -> String {
         .map(|line| line.replace('\\', "/"))
         .map(|line| line.replace(".exe", ""))
         .map(|line| re_time.replace_all(&line, "0.00s").to_string())
+        .map(|line| re_thread_id.replace_all(&line, "' panicked").to_string())
         .collect::<Vec<_>>();
 
     s.sort_unstable();
@@ -60,11 +76,46 @@ fn sanitize_lines(s: String) -> String {
     s.into_iter().join("\n")
 }
 
+/// Appends the first location line of every error to the error line itself.
+fn with_error_locations(s: &str) -> String {
+    let mut lines = s.lines().peekable();
+    let mut output = vec![];
+    while let Some(line) = lines.next() {
+        if line.starts_with("error") {
+            if let Some(location) = lines
+                .peek()
+                .filter(|next| next.trim_start().starts_with("-->"))
+            {
+                output.push(format!("{line} {}", location.trim_start()));
+                lines.next();
+                continue;
+            }
+        }
+        output.push(line.to_string());
+    }
+    output.join("\n")
+}
+
 #[test]
 fn cases_can_be_declared_on_async_methods() {
     run_acceptance_test!("cases_can_be_declared_on_async_methods")
 }
 
+#[test]
+fn cases_can_use_async_runtimes() {
+    run_acceptance_test!("cases_can_use_async_runtimes")
+}
+
+#[test]
+fn async_cases_require_runtime() {
+    run_acceptance_test!("async_cases_require_runtime")
+}
+
+#[test]
+fn cases_can_use_custom_test_attr() {
+    run_acceptance_test!("cases_can_use_custom_test_attr")
+}
+
 #[test]
 fn cases_can_be_declared_on_non_test_items() {
     run_acceptance_test!("cases_can_be_declared_on_non_test_items")
@@ -110,6 +161,21 @@ fn cases_support_generics() {
     run_acceptance_test!("cases_support_generics")
 }
 
+#[test]
+fn cases_support_sequences() {
+    run_acceptance_test!("cases_support_sequences")
+}
+
+#[test]
+fn cases_support_set_relations() {
+    run_acceptance_test!("cases_support_set_relations")
+}
+
+#[test]
+fn cases_support_map_assertions() {
+    run_acceptance_test!("cases_support_map_assertions")
+}
+
 #[test]
 fn cases_support_keyword_using() {
     run_acceptance_test!("cases_support_keyword_using")
@@ -135,6 +201,76 @@ fn cases_can_use_regex() {
     run_acceptance_test!("cases_can_use_regex")
 }
 
+#[test]
+fn cases_can_use_json() {
+    run_acceptance_test!("cases_can_use_json")
+}
+
+#[test]
+fn cases_explain_complex_failures() {
+    run_acceptance_test!("cases_explain_complex_failures")
+}
+
+#[test]
+fn cases_can_use_pretty_diff() {
+    run_acceptance_test!("cases_can_use_pretty_diff")
+}
+
+#[test]
+fn cases_can_use_snapshots() {
+    run_acceptance_test!("cases_can_use_snapshots")
+}
+
+#[test]
+fn cases_can_compare_with_files() {
+    run_acceptance_test!("cases_can_compare_with_files")
+}
+
+#[test]
+fn cases_support_path_assertions() {
+    run_acceptance_test!("cases_support_path_assertions")
+}
+
+#[test]
+fn cases_support_matchers() {
+    run_acceptance_test!("cases_support_matchers")
+}
+
+#[test]
+fn cases_can_be_declared_on_impl_blocks() {
+    run_acceptance_test!("cases_can_be_declared_on_impl_blocks")
+}
+
+#[test]
+fn cases_can_be_grouped_in_suites() {
+    run_acceptance_test!("cases_can_be_grouped_in_suites")
+}
+
+#[test]
+fn cases_can_use_case_sets() {
+    run_acceptance_test!("cases_can_use_case_sets")
+}
+
+#[test]
+fn cases_can_instantiate_generics() {
+    run_acceptance_test!("cases_can_instantiate_generics")
+}
+
+#[test]
+fn cases_support_keyword_arguments() {
+    run_acceptance_test!("cases_support_keyword_arguments")
+}
+
+#[test]
+fn cases_support_fixtures() {
+    run_acceptance_test!("cases_support_fixtures")
+}
+
+#[test]
+fn cases_support_setup_and_teardown() {
+    run_acceptance_test!("cases_support_setup_and_teardown")
+}
+
 #[test]
 fn features_produce_human_readable_errors() {
     run_acceptance_test!("features_produce_human_readable_errors")
@@ -149,3 +285,31 @@ fn allow_stays_on_fn() {
 fn matrices_compilation_errors() {
     run_acceptance_test!("matrices_compilation_errors")
 }
+
+#[test]
+fn impl_blocks_compilation_errors() {
+    run_acceptance_test!("impl_blocks_compilation_errors")
+}
+
+#[test]
+fn argument_count_compilation_errors() {
+    run_acceptance_test!("argument_count_compilation_errors")
+}
+
+#[test]
+fn misspelled_keywords_compilation_errors() {
+    run_acceptance_test!("misspelled_keywords_compilation_errors")
+}
+
+#[test]
+fn malformed_attributes_compilation_errors() {
+    run_acceptance_test!("malformed_attributes_compilation_errors")
+}
+
+#[test]
+fn cases_report_type_errors_at_expected_values() {
+    run_acceptance_test!(
+        with_locations,
+        "cases_report_type_errors_at_expected_values"
+    )
+}